@@ -59,13 +59,27 @@ pub struct EntityProperties {
     /// The default for 3D views is `true`, but for 2D views it is `false`.
     pub backproject_depth: EditableAutoValue<bool>,
 
+    /// The unit the depth texture is in, sets the default of [`Self::depth_from_world_scale`].
+    pub depth_unit: EditableAutoValue<DepthUnit>,
+
     /// How many depth units per world-space unit. e.g. 1000 for millimeters.
     ///
     /// This corresponds to [`re_log_types::component_types::Tensor::meter`].
     pub depth_from_world_scale: EditableAutoValue<f32>,
 
+    /// Whether the point radii follow [`Self::backproject_radius_scale`] or [`Self::backproject_radius_m`].
+    pub backproject_radius_mode: BackprojectRadiusMode,
+
     /// Used to scale the radii of the points in the resulting point cloud.
     pub backproject_radius_scale: EditableAutoValue<f32>,
+
+    /// Radius of the points in the resulting point cloud, in meters.
+    pub backproject_radius_m: f32,
+
+    /// Fraction of the backprojected points that are rendered, in `(0, 1]`.
+    ///
+    /// Only affects rendering, the logged depth data is left untouched.
+    pub point_cloud_render_fraction: f32,
 }
 
 #[cfg(feature = "serde")]
@@ -78,8 +92,12 @@ impl Default for EntityProperties {
             color_mapper: EditableAutoValue::default(),
             pinhole_image_plane_distance: EditableAutoValue::default(),
             backproject_depth: EditableAutoValue::Auto(true),
+            depth_unit: EditableAutoValue::default(),
             depth_from_world_scale: EditableAutoValue::default(),
+            backproject_radius_mode: BackprojectRadiusMode::default(),
             backproject_radius_scale: EditableAutoValue::Auto(1.0),
+            backproject_radius_m: 0.005,
+            point_cloud_render_fraction: 1.0,
         }
     }
 }
@@ -101,14 +119,19 @@ impl EntityProperties {
                 .clone(),
 
             backproject_depth: self.backproject_depth.or(&child.backproject_depth).clone(),
+            depth_unit: self.depth_unit.or(&child.depth_unit).clone(),
             depth_from_world_scale: self
                 .depth_from_world_scale
                 .or(&child.depth_from_world_scale)
                 .clone(),
+            backproject_radius_mode: child.backproject_radius_mode,
             backproject_radius_scale: self
                 .backproject_radius_scale
                 .or(&child.backproject_radius_scale)
                 .clone(),
+            backproject_radius_m: child.backproject_radius_m,
+            point_cloud_render_fraction: self.point_cloud_render_fraction
+                * child.point_cloud_render_fraction,
         }
     }
 }
@@ -189,6 +212,54 @@ impl Default for ColorMapper {
     }
 }
 
+/// The unit of the values in a depth texture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DepthUnit {
+    #[default]
+    Millimeters,
+    Meters,
+}
+
+impl DepthUnit {
+    /// How many depth steps make up one meter.
+    pub fn steps_per_meter(&self) -> f32 {
+        match self {
+            DepthUnit::Millimeters => 1000.0,
+            DepthUnit::Meters => 1.0,
+        }
+    }
+
+    /// Short form used next to values, e.g. "mm".
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            DepthUnit::Millimeters => "mm",
+            DepthUnit::Meters => "m",
+        }
+    }
+}
+
+impl std::fmt::Display for DepthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DepthUnit::Millimeters => "Millimeters",
+            DepthUnit::Meters => "Meters",
+        })
+    }
+}
+
+/// How the radii of backprojected depth points are sized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BackprojectRadiusMode {
+    /// A factor of the projected pixel size, so points grow with their depth.
+    #[default]
+    Scale,
+
+    /// The same physical size at every depth.
+    Meters,
+}
+
 // ----------------------------------------------------------------------------
 
 /// Get the latest value for a given [`re_log_types::Component`].
@@ -43,6 +43,9 @@ struct DepthCloudInfo {
     /// Configures color mapping mode, see `colormap.wgsl`.
     colormap: u32,
 
+    /// Added to the depth dependent point radius, in world-space units.
+    point_radius_in_world: f32,
+
     /// Changes between the opaque and outline draw-phases.
     radius_boost_in_ui_points: f32,
 };
@@ -108,7 +111,8 @@ fn compute_point_data(quad_idx: i32) -> PointData {
         let pos_in_world = depth_cloud_info.world_from_obj * Vec4(pos_in_obj, 1.0);
 
         data.pos_in_world = pos_in_world.xyz;
-        data.unresolved_radius = depth_cloud_info.point_radius_from_world_depth * world_space_depth;
+        data.unresolved_radius = depth_cloud_info.point_radius_from_world_depth * world_space_depth
+            + depth_cloud_info.point_radius_in_world;
         data.color = color;
     } else {
         // Degenerate case
@@ -61,8 +61,13 @@ mod gpu_data {
         /// Which colormap should be used.
         pub colormap: u32,
 
+        /// Added to the depth dependent point radius, in world-space units.
+        pub point_radius_in_world: f32,
+
         /// Changes over different draw-phases.
-        pub radius_boost_in_ui_points: wgpu_buffer_types::F32RowPadded,
+        pub radius_boost_in_ui_points: f32,
+
+        pub _padding: [u32; 2],
 
         pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 4 - 3 - 1 - 1 - 1],
     }
@@ -77,6 +82,7 @@ mod gpu_data {
                 depth_camera_intrinsics,
                 world_depth_from_data_depth,
                 point_radius_from_world_depth,
+                point_radius_in_world,
                 max_depth_in_world,
                 depth_dimensions: _,
                 depth_data,
@@ -100,7 +106,9 @@ mod gpu_data {
                 point_radius_from_world_depth: *point_radius_from_world_depth,
                 max_depth_in_world: *max_depth_in_world,
                 colormap: *colormap as u32,
-                radius_boost_in_ui_points: radius_boost_in_ui_points.into(),
+                point_radius_in_world: *point_radius_in_world,
+                radius_boost_in_ui_points,
+                _padding: Default::default(),
                 picking_layer_object_id: *picking_object_id,
                 end_padding: Default::default(),
             }
@@ -149,6 +157,9 @@ pub struct DepthCloud {
     /// Point radius is calculated as world-space depth times this value.
     pub point_radius_from_world_depth: f32,
 
+    /// Added to the depth dependent point radius, for points of a fixed world-space size.
+    pub point_radius_in_world: f32,
+
     /// The maximum depth value in world-space, for use with the colormap.
     pub max_depth_in_world: f32,
 
@@ -23,6 +23,7 @@ pub enum Command {
     OpenProfiler,
 
     ToggleMemoryPanel,
+    ToggleBandwidthPanel,
     ToggleBlueprintPanel,
     ToggleSelectionPanel,
     ToggleTimePanel,
@@ -41,6 +42,24 @@ pub enum Command {
 
     ToggleCommandPalette,
 
+    // Device streams:
+    ToggleColorStream,
+    ToggleLeftMonoStream,
+    ToggleRightMonoStream,
+    ToggleDepthStream,
+    TogglePointCloudStream,
+
+    // Devices:
+    SelectDevice1,
+    SelectDevice2,
+    SelectDevice3,
+    SelectDevice4,
+    SelectDevice5,
+    SelectDevice6,
+    SelectDevice7,
+    SelectDevice8,
+    SelectDevice9,
+
     // Playback:
     PlaybackTogglePlayPause,
     PlaybackStepBack,
@@ -89,6 +108,10 @@ impl Command {
                 "Toggle memory panel",
                 "Investigate what is using up RAM in Depthai Viewer",
             ),
+            Command::ToggleBandwidthPanel => (
+                "Toggle bandwidth panel",
+                "See how much data is being received from the device",
+            ),
             Command::ToggleBlueprintPanel => ("Toggle blueprint panel", "Toggle the left panel"),
             Command::ToggleSelectionPanel => ("Toggle selection panel", "Toggle the right panel"),
             Command::ToggleTimePanel => ("Toggle time panel", "Toggle the bottom time panel"),
@@ -114,6 +137,34 @@ impl Command {
                 ("Command palette…", "Toggle the command palette window")
             }
 
+            Command::ToggleColorStream => (
+                "Toggle color stream",
+                "Start or stop streaming the color camera",
+            ),
+            Command::ToggleLeftMonoStream => (
+                "Toggle left mono stream",
+                "Start or stop streaming the left mono camera",
+            ),
+            Command::ToggleRightMonoStream => (
+                "Toggle right mono stream",
+                "Start or stop streaming the right mono camera",
+            ),
+            Command::ToggleDepthStream => ("Toggle depth stream", "Start or stop streaming depth"),
+            Command::TogglePointCloudStream => (
+                "Toggle point cloud stream",
+                "Start or stop streaming the point cloud",
+            ),
+
+            Command::SelectDevice1 => ("Select device 1", "Switch to the first available device"),
+            Command::SelectDevice2 => ("Select device 2", "Switch to the second available device"),
+            Command::SelectDevice3 => ("Select device 3", "Switch to the third available device"),
+            Command::SelectDevice4 => ("Select device 4", "Switch to the fourth available device"),
+            Command::SelectDevice5 => ("Select device 5", "Switch to the fifth available device"),
+            Command::SelectDevice6 => ("Select device 6", "Switch to the sixth available device"),
+            Command::SelectDevice7 => ("Select device 7", "Switch to the seventh available device"),
+            Command::SelectDevice8 => ("Select device 8", "Switch to the eighth available device"),
+            Command::SelectDevice9 => ("Select device 9", "Switch to the ninth available device"),
+
             Command::PlaybackTogglePlayPause => {
                 ("Toggle play/pause", "Either play or pause the time")
             }
@@ -165,6 +216,7 @@ impl Command {
             #[cfg(not(target_arch = "wasm32"))]
             Command::OpenProfiler => Some(ctrl_shift(Key::P)),
             Command::ToggleMemoryPanel => Some(ctrl_shift(Key::M)),
+            Command::ToggleBandwidthPanel => Some(ctrl_shift(Key::W)),
             Command::ToggleBlueprintPanel => Some(ctrl_shift(Key::B)),
             Command::ToggleSelectionPanel => Some(ctrl_shift(Key::S)),
             Command::ToggleTimePanel => Some(ctrl_shift(Key::T)),
@@ -182,6 +234,23 @@ impl Command {
             Command::SelectionNext => Some(ctrl_shift(Key::ArrowRight)),
             Command::ToggleCommandPalette => Some(cmd(Key::P)),
 
+            // With a modifier, so typing digits into a value doesn't toggle streams.
+            Command::ToggleColorStream => Some(ctrl_shift(Key::Num1)),
+            Command::ToggleLeftMonoStream => Some(ctrl_shift(Key::Num2)),
+            Command::ToggleRightMonoStream => Some(ctrl_shift(Key::Num3)),
+            Command::ToggleDepthStream => Some(ctrl_shift(Key::Num4)),
+            Command::TogglePointCloudStream => Some(ctrl_shift(Key::Num5)),
+
+            Command::SelectDevice1 => Some(cmd(Key::Num1)),
+            Command::SelectDevice2 => Some(cmd(Key::Num2)),
+            Command::SelectDevice3 => Some(cmd(Key::Num3)),
+            Command::SelectDevice4 => Some(cmd(Key::Num4)),
+            Command::SelectDevice5 => Some(cmd(Key::Num5)),
+            Command::SelectDevice6 => Some(cmd(Key::Num6)),
+            Command::SelectDevice7 => Some(cmd(Key::Num7)),
+            Command::SelectDevice8 => Some(cmd(Key::Num8)),
+            Command::SelectDevice9 => Some(cmd(Key::Num9)),
+
             Command::PlaybackTogglePlayPause => Some(key(Key::Space)),
             Command::PlaybackStepBack => Some(key(Key::ArrowLeft)),
             Command::PlaybackStepForward => Some(key(Key::ArrowRight)),
@@ -189,6 +258,22 @@ impl Command {
         }
     }
 
+    /// Index into the available devices, for the device selection commands.
+    pub fn device_index(self) -> Option<usize> {
+        match self {
+            Command::SelectDevice1 => Some(0),
+            Command::SelectDevice2 => Some(1),
+            Command::SelectDevice3 => Some(2),
+            Command::SelectDevice4 => Some(3),
+            Command::SelectDevice5 => Some(4),
+            Command::SelectDevice6 => Some(5),
+            Command::SelectDevice7 => Some(6),
+            Command::SelectDevice8 => Some(7),
+            Command::SelectDevice9 => Some(8),
+            _ => None,
+        }
+    }
+
     #[must_use = "Returns the Command that was triggered by some keyboard shortcut"]
     pub fn listen_for_kb_shortcut(egui_ctx: &egui::Context) -> Option<Command> {
         use strum::IntoEnumIterator as _;
@@ -89,6 +89,8 @@ pub struct App {
     memory_panel: crate::memory_panel::MemoryPanel,
     memory_panel_open: bool,
 
+    bandwidth_panel: crate::bandwidth_panel::BandwidthPanel,
+
     latest_queue_interest: instant::Instant,
 
     /// Measures how long a frame takes to paint
@@ -148,6 +150,7 @@ impl App {
             toasts: toasts::Toasts::new(),
             memory_panel: Default::default(),
             memory_panel_open: false,
+            bandwidth_panel: Default::default(),
 
             latest_queue_interest: instant::Instant::now(), // TODO(emilk): `Instant::MIN` when we have our own `Instant` that supports it.
 
@@ -281,6 +284,9 @@ impl App {
             Command::ToggleMemoryPanel => {
                 self.memory_panel_open ^= true;
             }
+            Command::ToggleBandwidthPanel => {
+                self.state.show_bandwidth_panel ^= true;
+            }
             Command::ToggleBlueprintPanel => {
                 let blueprint = self.blueprint_mut(egui_ctx);
                 blueprint.blueprint_panel_expanded ^= true;
@@ -336,6 +342,46 @@ impl App {
                 self.cmd_palette.toggle();
             }
 
+            Command::ToggleColorStream => {
+                self.state
+                    .depthai_state
+                    .toggle_subscription(depthai::ChannelId::ColorImage);
+            }
+            Command::ToggleLeftMonoStream => {
+                self.state
+                    .depthai_state
+                    .toggle_subscription(depthai::ChannelId::LeftMono);
+            }
+            Command::ToggleRightMonoStream => {
+                self.state
+                    .depthai_state
+                    .toggle_subscription(depthai::ChannelId::RightMono);
+            }
+            Command::ToggleDepthStream => {
+                self.state
+                    .depthai_state
+                    .toggle_subscription(depthai::ChannelId::DepthImage);
+            }
+            Command::TogglePointCloudStream => {
+                self.state
+                    .depthai_state
+                    .toggle_subscription(depthai::ChannelId::PointCloud);
+            }
+
+            Command::SelectDevice1
+            | Command::SelectDevice2
+            | Command::SelectDevice3
+            | Command::SelectDevice4
+            | Command::SelectDevice5
+            | Command::SelectDevice6
+            | Command::SelectDevice7
+            | Command::SelectDevice8
+            | Command::SelectDevice9 => {
+                if let Some(index) = cmd.device_index() {
+                    self.state.depthai_state.select_device_by_index(index);
+                }
+            }
+
             Command::PlaybackTogglePlayPause => {
                 self.run_time_control_command(TimeControlCommand::TogglePlayPause);
             }
@@ -421,6 +467,25 @@ impl App {
                 );
             });
     }
+
+    fn bandwidth_panel_ui(&mut self, ui: &mut egui::Ui) {
+        let frame = egui::Frame {
+            fill: ui.visuals().panel_fill,
+            ..self.re_ui.bottom_panel_frame()
+        };
+
+        let response = egui::SidePanel::right("bandwidth_panel")
+            .default_width(self.state.bandwidth_panel_width.unwrap_or(300.0))
+            .resizable(true)
+            .frame(frame)
+            .show_animated_inside(ui, self.state.show_bandwidth_panel, |ui| {
+                self.bandwidth_panel.ui(ui, &mut self.state.depthai_state);
+            });
+        // Only set once the panel is fully open, not while it animates.
+        if let Some(response) = response {
+            self.state.bandwidth_panel_width = Some(response.response.rect.width());
+        }
+    }
 }
 
 impl eframe::App for App {
@@ -442,7 +507,33 @@ impl eframe::App for App {
 
     fn update(&mut self, egui_ctx: &egui::Context, frame: &mut eframe::Frame) {
         let frame_start = Instant::now();
-        self.state.depthai_state.update(); // Always update depthai state
+        // Always update depthai state
+        let playback = self
+            .log_dbs
+            .get(&self.state.selected_rec_id)
+            .and_then(|log_db| log_db.data_source.as_ref())
+            .map_or(false, |source| {
+                matches!(
+                    source,
+                    re_smart_channel::Source::File { .. }
+                        | re_smart_channel::Source::RrdHttpStream { .. }
+                        | re_smart_channel::Source::RrdWebEventListener
+                )
+            });
+        self.state.depthai_state.set_playback(playback);
+        self.state
+            .depthai_state
+            .backend_comms
+            .ws
+            .set_verbose(self.state.app_options.log_backend_messages);
+        #[cfg(not(target_arch = "wasm32"))]
+        if std::mem::take(&mut self.state.depthai_state.load_recording_requested) {
+            open(self);
+        }
+        self.state.depthai_state.update(
+            self.state.app_options.device_poll_interval,
+            egui_ctx.input(|i| i.raw.has_focus),
+        );
 
         if self.startup_options.memory_limit.limit.is_none() {
             // we only warn about high memory usage if the user hasn't specified a limit
@@ -496,6 +587,7 @@ impl eframe::App for App {
 
         // do first, before doing too many allocations
         self.memory_panel.update(&gpu_resource_stats, &store_stats);
+        self.bandwidth_panel.update(&mut self.state.depthai_state);
 
         self.check_keyboard_shortcuts(egui_ctx);
 
@@ -525,6 +617,7 @@ impl eframe::App for App {
                 top_panel(ui, frame, self, &gpu_resource_stats);
 
                 self.memory_panel_ui(ui, &gpu_resource_stats, &store_config, &store_stats);
+                self.bandwidth_panel_ui(ui);
 
                 let log_db = self.log_dbs.entry(self.state.selected_rec_id).or_default();
                 let selected_app_id = log_db
@@ -706,6 +799,14 @@ impl App {
         let start = instant::Instant::now();
 
         while let Ok(msg) = self.rx.try_recv() {
+            self.bandwidth_panel.on_log_msg(&msg);
+            self.state.depthai_state.on_log_msg(&msg);
+            if self.state.depthai_state.throttle(&msg) {
+                continue;
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            self.state.depthai_state.record(&msg);
+
             // All messages except [`LogMsg::GoodBye`] should have an associated recording id
             if let Some(recording_id) = msg.recording_id() {
                 let is_new_recording = if let LogMsg::BeginRecordingMsg(msg) = &msg {
@@ -725,6 +826,11 @@ impl App {
                 if let Err(err) = log_db.add(&msg) {
                     re_log::error!("Failed to add incoming msg: {err}");
                 };
+                if let Some(scalar_msg) = self.state.depthai_state.imu_scalar_msg(&msg) {
+                    if let Err(err) = log_db.add(&scalar_msg) {
+                        re_log::error!("Failed to add the IMU scalars: {err}");
+                    }
+                }
 
                 if is_new_recording {
                     // Do analytics after ingesting the new message,
@@ -963,6 +1069,11 @@ struct AppState {
     selected_device: depthai::DeviceId,
     depthai_state: depthai::State,
 
+    /// Kept across restarts, so users who don't care about bandwidth can hide it for good.
+    show_bandwidth_panel: bool,
+    /// Width the bandwidth panel was resized to, `None` for the default.
+    bandwidth_panel_width: Option<f32>,
+
     #[cfg(not(target_arch = "wasm32"))]
     #[serde(skip)]
     profiler: crate::Profiler,
@@ -1174,6 +1285,12 @@ fn rerun_menu_button_ui(ui: &mut egui::Ui, frame: &mut eframe::Frame, app: &mut
             Command::OpenProfiler.menu_button_ui(ui, &mut app.pending_commands);
 
             Command::ToggleMemoryPanel.menu_button_ui(ui, &mut app.pending_commands);
+            ui.checkbox(&mut app.state.show_bandwidth_panel, "Bandwidth panel")
+                .on_hover_text(format!(
+                    "{}{}",
+                    Command::ToggleBandwidthPanel.tooltip(),
+                    Command::ToggleBandwidthPanel.format_shortcut_tooltip_suffix(ui.ctx())
+                ));
         }
 
         ui.add_space(spacing);
@@ -1256,6 +1373,8 @@ fn top_bar_ui(
         input_latency_label_ui(ui, app);
     }
 
+    bandwidth_warning_ui(ui, app);
+
     if let Some(log_db) = app.log_dbs.get(&app.state.selected_rec_id) {
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             let selected_app_id = log_db
@@ -1426,6 +1545,20 @@ fn input_latency_label_ui(ui: &mut egui::Ui, app: &mut App) {
     }
 }
 
+fn bandwidth_warning_ui(ui: &mut egui::Ui, app: &mut App) {
+    let Some(warning) = app.bandwidth_panel.link_warning() else {
+        return;
+    };
+    ui.separator();
+    if ui
+        .add(egui::Label::new(app.re_ui.error_text("⚠ Bandwidth")).sense(egui::Sense::click()))
+        .on_hover_text(format!("{warning}\nClick to open the bandwidth panel."))
+        .clicked()
+    {
+        app.state.show_bandwidth_panel = true;
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 const FILE_SAVER_PROMISE: &str = "file_saver";
@@ -1618,6 +1751,20 @@ fn options_menu_ui(ui: &mut egui::Ui, _frame: &mut eframe::Frame, options: &mut
         ui.close_menu();
     }
 
+    ui.horizontal(|ui| {
+        ui.label("Device poll interval:");
+        ui.add(
+            egui::DragValue::new(&mut options.device_poll_interval)
+                .clamp_range(0.5..=60.0)
+                .speed(0.1)
+                .suffix(" s"),
+        )
+        .on_hover_text(
+            "How often to look for connected devices while none is selected. \
+            Paused while the window isn't focused.",
+        );
+    });
+
     #[cfg(not(target_arch = "wasm32"))]
     {
         if ui
@@ -1702,6 +1849,11 @@ fn debug_menu_options_ui(ui: &mut egui::Ui, options: &mut AppOptions, _frame: &m
     )
     .on_hover_text("Show a debug overlay that renders the picking layer information using the `debug_overlay.wgsl` shader.");
 
+    ui.checkbox(&mut options.log_backend_messages, "Log backend messages")
+        .on_hover_text(
+            "Log every message exchanged with the depthai backend. Very verbose while streaming.",
+        );
+
     ui.menu_button("Crash", |ui| {
         #[allow(clippy::manual_assert)]
         if ui.button("panic!").clicked() {
@@ -1,6 +1,7 @@
 use super::depthai;
 use super::ws::{BackWsMessage as WsMessage, WebSocket, WsMessageData, WsMessageType};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 const DEPTHAI_API_URL: &str = "http://localhost:8000";
 
@@ -17,15 +18,304 @@ impl Default for ApiError {
     }
 }
 
+/// The pipeline in the shape the backend expects, kept apart from [`depthai::DeviceConfig`]
+/// so that changing the UI side can't silently change what is sent.
+#[derive(Serialize, Clone, fmt::Debug)]
+pub struct PipelineRequest {
+    /// The [`depthai::API_VERSION`] the request was written for.
+    pub version: u32,
+    pub color_camera: ColorCameraRequest,
+    pub left_camera: MonoCameraRequest,
+    pub right_camera: MonoCameraRequest,
+    pub depth_enabled: bool,
+    pub depth: Option<DepthRequest>,
+    pub ai_model: AiModelRequest,
+    pub ai_config: AiConfigRequest,
+}
+
+/// A pipeline to check without starting it, the backend's verdict carries the same `request_id`.
+#[derive(Serialize, Clone, fmt::Debug)]
+pub struct ValidateConfigRequest {
+    pub request_id: u32,
+    #[serde(flatten)]
+    pub pipeline: PipelineRequest,
+}
+
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct ColorCameraRequest {
+    pub enabled: bool,
+    pub fps: u8,
+    pub resolution: depthai::ColorCameraResolution,
+    pub board_socket: depthai::BoardSocket,
+    pub auto_exposure: bool,
+    pub exposure_us: u32,
+    pub iso: u32,
+    pub auto_white_balance: bool,
+    pub white_balance_k: u16,
+    pub auto_focus: bool,
+    pub focus_position: u8,
+    pub orientation: depthai::CameraImageOrientation,
+    pub isp_scale: (u8, u8),
+    pub encoding: depthai::ColorEncoding,
+    pub crop_roi: Option<(f32, f32, f32, f32)>,
+}
+
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct MonoCameraRequest {
+    pub enabled: bool,
+    pub fps: u8,
+    pub resolution: depthai::MonoCameraResolution,
+    pub board_socket: depthai::BoardSocket,
+    pub orientation: depthai::CameraImageOrientation,
+}
+
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct DepthRequest {
+    pub median: depthai::DepthMedianFilter,
+    pub preset: depthai::DepthProfilePreset,
+    pub pointcloud: PointcloudRequest,
+    pub align: depthai::BoardSocket,
+    pub filters: DepthFiltersRequest,
+    pub decimation_factor: u8,
+    pub confidence_threshold: u8,
+    pub disparity_shift: u16,
+    pub stream_disparity: bool,
+    pub stream_confidence: bool,
+}
+
+/// The point count the backend reports back isn't part of the request.
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct PointcloudRequest {
+    pub enabled: bool,
+    pub voxel_size_m: f32,
+}
+
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct DepthFiltersRequest {
+    pub spatial: bool,
+    pub temporal: bool,
+    pub speckle: bool,
+    pub threshold_min: u16,
+    pub threshold_max: u16,
+}
+
+#[derive(Serialize, Clone, fmt::Debug)]
+pub struct AiModelRequest {
+    pub path: String,
+    pub display_name: String,
+}
+
+#[derive(Serialize, Clone, Copy, fmt::Debug)]
+pub struct AiConfigRequest {
+    pub confidence_threshold: f32,
+    pub spatial: bool,
+}
+
+impl From<&depthai::DeviceConfig> for PipelineRequest {
+    fn from(config: &depthai::DeviceConfig) -> Self {
+        Self {
+            version: depthai::API_VERSION,
+            color_camera: (&config.color_camera).into(),
+            left_camera: (&config.left_camera).into(),
+            right_camera: (&config.right_camera).into(),
+            depth_enabled: config.depth_enabled,
+            depth: config.depth.as_ref().map(Into::into),
+            ai_model: (&config.ai_model).into(),
+            ai_config: (&config.ai_config).into(),
+        }
+    }
+}
+
+impl From<&depthai::ColorCameraConfig> for ColorCameraRequest {
+    fn from(config: &depthai::ColorCameraConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            fps: config.fps,
+            resolution: config.resolution,
+            board_socket: config.board_socket,
+            auto_exposure: config.auto_exposure,
+            exposure_us: config.exposure_us,
+            iso: config.iso,
+            auto_white_balance: config.auto_white_balance,
+            white_balance_k: config.white_balance_k,
+            auto_focus: config.auto_focus,
+            focus_position: config.focus_position,
+            orientation: config.orientation,
+            isp_scale: config.isp_scale,
+            encoding: config.encoding,
+            crop_roi: config.crop_roi,
+        }
+    }
+}
+
+impl From<&depthai::MonoCameraConfig> for MonoCameraRequest {
+    fn from(config: &depthai::MonoCameraConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            fps: config.fps,
+            resolution: config.resolution,
+            board_socket: config.board_socket,
+            orientation: config.orientation,
+        }
+    }
+}
+
+impl From<&depthai::DepthConfig> for DepthRequest {
+    fn from(config: &depthai::DepthConfig) -> Self {
+        Self {
+            median: config.median,
+            preset: config.preset,
+            pointcloud: PointcloudRequest {
+                enabled: config.pointcloud.enabled,
+                voxel_size_m: config.pointcloud.voxel_size_m,
+            },
+            align: config.align,
+            filters: DepthFiltersRequest {
+                spatial: config.filters.spatial,
+                temporal: config.filters.temporal,
+                speckle: config.filters.speckle,
+                threshold_min: config.filters.threshold_min,
+                threshold_max: config.filters.threshold_max,
+            },
+            decimation_factor: config.decimation_factor,
+            confidence_threshold: config.confidence_threshold,
+            disparity_shift: config.disparity_shift,
+            stream_disparity: config.stream_disparity,
+            stream_confidence: config.stream_confidence,
+        }
+    }
+}
+
+impl From<&depthai::AiModel> for AiModelRequest {
+    fn from(model: &depthai::AiModel) -> Self {
+        Self {
+            path: model.path.clone(),
+            display_name: model.display_name.clone(),
+        }
+    }
+}
+
+impl From<&depthai::AiConfig> for AiConfigRequest {
+    fn from(config: &depthai::AiConfig) -> Self {
+        Self {
+            confidence_threshold: config.confidence_threshold,
+            spatial: config.spatial,
+        }
+    }
+}
+
+/// A [`PipelineRequest`] the backend would reject.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Invalid pipeline request, offending fields: {}", .fields.join(", "))]
+pub struct InvalidPipelineRequest {
+    /// Wire names of the offending fields, e.g. `color_camera.fps`.
+    pub fields: Vec<&'static str>,
+}
+
+impl PipelineRequest {
+    /// Check the invariants the backend relies on, so a bad request fails here with a useful error.
+    pub fn validate(&self) -> Result<(), InvalidPipelineRequest> {
+        let mut fields = Vec::new();
+        let color = &self.color_camera;
+        if color.fps == 0 || color.fps > color.resolution.max_fps() {
+            fields.push("color_camera.fps");
+        }
+        if !depthai::is_valid_isp_scale(color.isp_scale) {
+            fields.push("color_camera.isp_scale");
+        }
+        if !depthai::is_valid_crop_roi(color.crop_roi) {
+            fields.push("color_camera.crop_roi");
+        }
+        let (left, right) = (&self.left_camera, &self.right_camera);
+        if left.fps == 0 || left.fps > left.resolution.max_fps() {
+            fields.push("left_camera.fps");
+        }
+        if right.fps == 0 || right.fps > right.resolution.max_fps() {
+            fields.push("right_camera.fps");
+        }
+        if self.depth_enabled && self.depth.is_none() {
+            fields.push("depth");
+        }
+        if let Some(depth) = &self.depth {
+            if !left.enabled {
+                fields.push("left_camera.enabled");
+            }
+            if !right.enabled {
+                fields.push("right_camera.enabled");
+            }
+            if left.resolution != right.resolution {
+                fields.push("right_camera.resolution");
+            }
+            if left.fps != right.fps {
+                fields.push("right_camera.fps");
+            }
+            // The backend falls back to the usual stereo pair for sockets left on AUTO.
+            let resolve = |socket: depthai::BoardSocket, fallback| match socket {
+                depthai::BoardSocket::AUTO => fallback,
+                socket => socket,
+            };
+            if resolve(left.board_socket, depthai::BoardSocket::LEFT)
+                == resolve(right.board_socket, depthai::BoardSocket::RIGHT)
+            {
+                fields.push("right_camera.board_socket");
+            }
+            if !matches!(
+                depth.align,
+                depthai::BoardSocket::RGB | depthai::BoardSocket::RIGHT
+            ) {
+                fields.push("depth.align");
+            }
+            if depth.decimation_factor == 0 {
+                fields.push("depth.decimation_factor");
+            }
+            if depth.disparity_shift > depthai::DepthConfig::MAX_DISPARITY_SHIFT {
+                fields.push("depth.disparity_shift");
+            }
+            if depth.filters.threshold_min >= depth.filters.threshold_max {
+                fields.push("depth.filters");
+            }
+        }
+        if !(0.0..=1.0).contains(&self.ai_config.confidence_threshold) {
+            fields.push("ai_config.confidence_threshold");
+        }
+        fields.sort_unstable();
+        fields.dedup();
+        if fields.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidPipelineRequest { fields })
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct BackendCommChannel {
     pub ws: WebSocket,
+    /// The last pipeline request, as it was sent to the backend.
+    pub sent_pipeline_json: Option<String>,
+    /// The backend's last reply to a pipeline request, the pipeline it runs or an error, as it was received.
+    pub received_pipeline_json: Option<String>,
 }
 
 impl BackendCommChannel {
     pub fn shutdown(&mut self) {
         self.ws.shutdown();
     }
+    /// Tells the backend which viewer it's talking to, it replies with its own version.
+    pub fn hello(&mut self) {
+        self.ws.send(
+            serde_json::to_string(&WsMessage {
+                kind: WsMessageType::Hello,
+                data: WsMessageData::Hello(depthai::Hello {
+                    viewer_version: env!("CARGO_PKG_VERSION").to_owned(),
+                    api_version: depthai::API_VERSION,
+                    ..Default::default()
+                }),
+            })
+            .unwrap(),
+        );
+    }
+
     pub fn set_subscriptions(&mut self, subscriptions: &Vec<depthai::ChannelId>) {
         self.ws.send(
             serde_json::to_string(&WsMessage {
@@ -36,18 +326,55 @@ impl BackendCommChannel {
         );
     }
 
-    pub fn set_pipeline(&mut self, config: &depthai::DeviceConfig) {
+    pub fn set_pipeline(&mut self, request: &PipelineRequest) {
+        let json = serde_json::to_string(&WsMessage {
+            kind: WsMessageType::Pipeline,
+            data: WsMessageData::PipelineRequest(request.clone()),
+        })
+        .unwrap();
+        self.sent_pipeline_json = Some(json.clone());
+        self.ws.send(json);
+    }
+
+    pub fn validate_config(&mut self, request_id: u32, request: &PipelineRequest) {
         self.ws.send(
             serde_json::to_string(&WsMessage {
-                kind: WsMessageType::Pipeline,
-                data: WsMessageData::Pipeline(config.clone()),
+                kind: WsMessageType::ValidateConfig,
+                data: WsMessageData::ValidateConfigRequest(ValidateConfigRequest {
+                    request_id,
+                    pipeline: request.clone(),
+                }),
+            })
+            .unwrap(),
+        );
+    }
+
+    pub fn set_calibration(&mut self, calibration: serde_json::Value) {
+        self.ws.send(
+            serde_json::to_string(&WsMessage {
+                kind: WsMessageType::Calibration,
+                data: WsMessageData::Calibration(calibration),
+            })
+            .unwrap(),
+        );
+    }
+
+    pub fn request_frame(&mut self, channels: &[depthai::ChannelId]) {
+        self.ws.send(
+            serde_json::to_string(&WsMessage {
+                kind: WsMessageType::RequestFrame,
+                data: WsMessageData::RequestFrame(channels.to_vec()),
             })
             .unwrap(),
         );
     }
 
     pub fn receive(&mut self) -> Option<WsMessage> {
-        self.ws.receive()
+        let (message, json) = self.ws.receive()?;
+        if matches!(message.kind, WsMessageType::Pipeline | WsMessageType::Error) {
+            self.received_pipeline_json = Some(json);
+        }
+        Some(message)
     }
 
     pub fn get_devices(&mut self) {
@@ -63,7 +390,41 @@ impl BackendCommChannel {
         self.ws.send(
             serde_json::to_string(&WsMessage {
                 kind: WsMessageType::Device,
-                data: WsMessageData::Device(depthai::Device { id: device_id }),
+                data: WsMessageData::Device(depthai::Device {
+                    id: device_id,
+                    ..Default::default()
+                }),
+            })
+            .unwrap(),
+        );
+    }
+
+    /// The backend closes the current device when asked to select no device.
+    pub fn release_device(&mut self) {
+        self.set_device(depthai::DeviceId::default());
+    }
+
+    pub fn force_release_device(&mut self, device_id: depthai::DeviceId) {
+        self.ws.send(
+            serde_json::to_string(&WsMessage {
+                kind: WsMessageType::ForceRelease,
+                data: WsMessageData::ForceRelease(depthai::Device {
+                    id: device_id,
+                    ..Default::default()
+                }),
+            })
+            .unwrap(),
+        );
+    }
+
+    pub fn capture_still(&mut self, device_id: depthai::DeviceId) {
+        self.ws.send(
+            serde_json::to_string(&WsMessage {
+                kind: WsMessageType::CaptureStill,
+                data: WsMessageData::CaptureStill(depthai::Device {
+                    id: device_id,
+                    ..Default::default()
+                }),
             })
             .unwrap(),
         );
@@ -1,12 +1,12 @@
 use itertools::Itertools;
-use re_data_store::EntityPropertyMap;
-use re_log_types::{EntityPath, EntityPathHash};
+use re_data_store::{ColorMapper, Colormap, EditableAutoValue, EntityPropertyMap};
+use re_log_types::{EntityPath, EntityPathHash, ImuData};
 use std::collections::{BTreeSet, HashMap};
 
 use crate::ui::SpaceViewId;
 
 use super::super::ui::SpaceView;
-use super::api::BackendCommChannel;
+use super::api::{BackendCommChannel, PipelineRequest};
 use super::ws::{BackWsMessage as WsMessage, WsMessageData, WsMessageType};
 use instant::Instant;
 use std::fmt;
@@ -23,6 +23,8 @@ pub enum ColorCameraResolution {
 #[allow(non_camel_case_types)]
 pub enum MonoCameraResolution {
     THE_400_P,
+    THE_720_P,
+    THE_800_P,
 }
 
 // fmt::Display is used in UI while fmt::Debug is used with the depthai backend api
@@ -35,40 +37,271 @@ impl fmt::Display for ColorCameraResolution {
     }
 }
 
+/// The lowest frame rate the cameras can be run at.
+pub const MIN_FPS: u8 = 1;
+
+impl ColorCameraResolution {
+    pub const ALL: [Self; 2] = [Self::THE_1080_P, Self::THE_4_K];
+
+    /// The highest frame rate the color sensor supports at this resolution.
+    pub fn max_fps(&self) -> u8 {
+        match self {
+            Self::THE_1080_P => 60,
+            Self::THE_4_K => 30,
+        }
+    }
+}
+
+impl MonoCameraResolution {
+    pub const ALL: [Self; 3] = [Self::THE_400_P, Self::THE_720_P, Self::THE_800_P];
+
+    /// The highest frame rate the mono sensors support at this resolution.
+    pub fn max_fps(&self) -> u8 {
+        match self {
+            Self::THE_400_P | Self::THE_720_P | Self::THE_800_P => 120,
+        }
+    }
+}
+
 impl fmt::Display for MonoCameraResolution {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::THE_400_P => write!(f, "400p"),
+            Self::THE_720_P => write!(f, "720p"),
+            Self::THE_800_P => write!(f, "800p"),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, fmt::Debug, PartialEq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum CameraImageOrientation {
+    AUTO,
+    NORMAL,
+    ROTATE_180,
+    MIRROR,
+}
+
+impl Default for CameraImageOrientation {
+    fn default() -> Self {
+        Self::AUTO
+    }
+}
+
+impl fmt::Display for CameraImageOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AUTO => write!(f, "Auto"),
+            Self::NORMAL => write!(f, "Normal"),
+            Self::ROTATE_180 => write!(f, "Rotate 180°"),
+            Self::MIRROR => write!(f, "Mirror"),
+        }
+    }
+}
+
+impl CameraImageOrientation {
+    pub const ALL: [Self; 4] = [Self::AUTO, Self::NORMAL, Self::ROTATE_180, Self::MIRROR];
+}
+
+/// How the device encodes the color frames before sending them to the host.
+///
+/// `MJPEG` frames are logged as jpeg tensors, which the viewer decodes for display.
+/// The viewer has no video decoder, so `H264` and `H265` only lower the bandwidth between the device
+/// and the backend, which decodes them before logging.
+#[derive(serde::Deserialize, serde::Serialize, fmt::Debug, PartialEq, Clone, Copy, Default)]
+#[allow(non_camel_case_types)]
+pub enum ColorEncoding {
+    /// Also what configs saved with an encoding this viewer doesn't know fall back to.
+    #[default]
+    #[serde(other)]
+    RAW,
+    MJPEG,
+    H264,
+    H265,
+}
+
+impl fmt::Display for ColorEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RAW => write!(f, "Raw"),
+            Self::MJPEG => write!(f, "MJPEG"),
+            Self::H264 => write!(f, "H.264"),
+            Self::H265 => write!(f, "H.265"),
+        }
+    }
+}
+
+impl ColorEncoding {
+    pub const ALL: [Self; 4] = [Self::RAW, Self::MJPEG, Self::H264, Self::H265];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::RAW => "Uncompressed, the most bandwidth but no compression artifacts",
+            Self::MJPEG => "Every frame compressed on its own, decoded by the viewer",
+            Self::H264 | Self::H265 => {
+                "Compressed video, the least bandwidth over the link to the device. \
+                Decoded by the backend, so it doesn't lower what the viewer receives"
+            }
         }
     }
 }
 
+/// Manual exposure time range of the color camera, in microseconds.
+pub const COLOR_CAMERA_EXPOSURE_RANGE_US: std::ops::RangeInclusive<u32> = 1..=33000;
+/// Manual sensitivity range of the color camera.
+pub const COLOR_CAMERA_ISO_RANGE: std::ops::RangeInclusive<u32> = 100..=1600;
+/// Manual white balance range of the color camera, in kelvin.
+pub const COLOR_CAMERA_WHITE_BALANCE_RANGE_K: std::ops::RangeInclusive<u16> = 2000..=12000;
+
+/// The color camera frames are logged here. Unlike the mono cameras they sit one level deeper,
+/// under the color camera's pinhole at `world/camera/image`, so 2D views of the color camera can
+/// overlay the detections and the aligned depth, and the 3D view can place them in front of it.
+pub const COLOR_IMAGE_ENTITY_PATH: &str = "world/camera/image/rgb";
+
 #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(default)]
 pub struct ColorCameraConfig {
+    /// Whether the camera node is part of the pipeline at all.
+    pub enabled: bool,
     pub fps: u8,
     pub resolution: ColorCameraResolution,
+    /// Boards with non-standard camera modules may have the color camera on another socket.
+    pub board_socket: BoardSocket,
+    pub auto_exposure: bool,
+    /// Only used when `auto_exposure` is off.
+    pub exposure_us: u32,
+    /// Only used when `auto_exposure` is off.
+    pub iso: u32,
+    pub auto_white_balance: bool,
+    /// Only used when `auto_white_balance` is off.
+    pub white_balance_k: u16,
+    /// Only has an effect on modules with a motorized focus lens.
+    pub auto_focus: bool,
+    /// Lens position, from 0 (far) to 255 (near). Only used when `auto_focus` is off.
+    pub focus_position: u8,
+    pub orientation: CameraImageOrientation,
+    /// Numerator and denominator the ISP scales the color image by, lowers bandwidth at high resolutions.
+    pub isp_scale: (u8, u8),
+    pub encoding: ColorEncoding,
+    /// Only stream this region of the frame, as `(x_min, y_min, x_max, y_max)` relative to the full frame.
+    pub crop_roi: Option<(f32, f32, f32, f32)>,
+}
+
+/// The ISP can only downscale, so the fraction has to be in (0, 1].
+pub fn is_valid_isp_scale((numerator, denominator): (u8, u8)) -> bool {
+    numerator > 0 && denominator > 0 && numerator <= denominator
+}
+
+pub fn is_valid_crop_roi(crop_roi: Option<(f32, f32, f32, f32)>) -> bool {
+    crop_roi.map_or(true, |(x_min, y_min, x_max, y_max)| {
+        0.0 <= x_min
+            && x_min < x_max
+            && x_max <= 1.0
+            && 0.0 <= y_min
+            && y_min < y_max
+            && y_max <= 1.0
+    })
+}
+
+impl ColorCameraConfig {
+    pub fn isp_scale_is_valid(&self) -> bool {
+        is_valid_isp_scale(self.isp_scale)
+    }
+
+    pub fn crop_roi_is_valid(&self) -> bool {
+        is_valid_crop_roi(self.crop_roi)
+    }
+
+    /// `region` is relative to the frame as it's streamed, which is already cropped to [`Self::crop_roi`],
+    /// returns it relative to the full frame.
+    pub fn crop_within(&self, region: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (x_min, y_min, x_max, y_max) = self.crop_roi.unwrap_or((0.0, 0.0, 1.0, 1.0));
+        let (width, height) = (x_max - x_min, y_max - y_min);
+        (
+            x_min + region.0 * width,
+            y_min + region.1 * height,
+            x_min + region.2 * width,
+            y_min + region.3 * height,
+        )
+    }
 }
 
 impl Default for ColorCameraConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             fps: 30,
             resolution: ColorCameraResolution::THE_1080_P,
+            board_socket: BoardSocket::RGB,
+            auto_exposure: true,
+            exposure_us: 20000,
+            iso: 800,
+            auto_white_balance: true,
+            white_balance_k: 5500,
+            auto_focus: true,
+            focus_position: 130,
+            orientation: CameraImageOrientation::AUTO,
+            isp_scale: (1, 1),
+            encoding: ColorEncoding::default(),
+            crop_roi: None,
         }
     }
 }
 
+impl BoardSocket {
+    /// Sockets the color camera can be connected to.
+    pub const COLOR: [Self; 10] = [
+        Self::RGB,
+        Self::CENTER,
+        Self::CAM_A,
+        Self::CAM_B,
+        Self::CAM_C,
+        Self::CAM_D,
+        Self::CAM_E,
+        Self::CAM_F,
+        Self::CAM_G,
+        Self::CAM_H,
+    ];
+
+    /// Sockets a mono camera of a stereo pair can be connected to.
+    pub const MONO: [Self; 11] = [
+        Self::LEFT,
+        Self::RIGHT,
+        Self::CENTER,
+        Self::CAM_A,
+        Self::CAM_B,
+        Self::CAM_C,
+        Self::CAM_D,
+        Self::CAM_E,
+        Self::CAM_F,
+        Self::CAM_G,
+        Self::CAM_H,
+    ];
+}
+
 impl fmt::Debug for ColorCameraConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Color camera config: fps: {}, resolution: {:?}",
-            self.fps, self.resolution,
+            "Color camera config: fps: {}, resolution: {:?}, socket: {:?}, auto exposure: {}, exposure: {}us, iso: {}, auto white balance: {}, white balance: {}K, auto focus: {}, focus: {}, orientation: {:?}, isp scale: {}/{}",
+            self.fps,
+            self.resolution,
+            self.board_socket,
+            self.auto_exposure,
+            self.exposure_us,
+            self.iso,
+            self.auto_white_balance,
+            self.white_balance_k,
+            self.auto_focus,
+            self.focus_position,
+            self.orientation,
+            self.isp_scale.0,
+            self.isp_scale.1,
         )
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
 #[allow(non_camel_case_types)]
 pub enum BoardSocket {
     AUTO,
@@ -88,17 +321,24 @@ pub enum BoardSocket {
 
 #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
 pub struct MonoCameraConfig {
+    /// Whether the camera node is part of the pipeline at all, depth needs both mono cameras.
+    #[serde(default = "bool_true")]
+    pub enabled: bool,
     pub fps: u8,
     pub resolution: MonoCameraResolution,
     pub board_socket: BoardSocket,
+    #[serde(default)]
+    pub orientation: CameraImageOrientation,
 }
 
 impl Default for MonoCameraConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             fps: 30,
             resolution: MonoCameraResolution::THE_400_P,
             board_socket: BoardSocket::AUTO,
+            orientation: CameraImageOrientation::AUTO,
         }
     }
 }
@@ -107,13 +347,13 @@ impl fmt::Debug for MonoCameraConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Mono camera config: fps: {}, resolution: {:?}",
-            self.fps, self.resolution,
+            "Mono camera config: fps: {}, resolution: {:?}, orientation: {:?}",
+            self.fps, self.resolution, self.orientation,
         )
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
 #[allow(non_camel_case_types)]
 pub enum DepthProfilePreset {
     HIGH_DENSITY,
@@ -126,6 +366,20 @@ impl Default for DepthProfilePreset {
     }
 }
 
+impl DepthProfilePreset {
+    pub const ALL: [Self; 2] = [Self::HIGH_DENSITY, Self::HIGH_ACCURACY];
+
+    /// What the preset trades off, for tooltips.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::HIGH_DENSITY => {
+                "Fills in more pixels, at the cost of more noise and some invalid depth"
+            }
+            Self::HIGH_ACCURACY => "Sparser depth, but the pixels that remain are cleaner",
+        }
+    }
+}
+
 impl fmt::Display for DepthProfilePreset {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -150,25 +404,175 @@ impl Default for DepthMedianFilter {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default, fmt::Debug)]
+impl fmt::Display for DepthMedianFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MEDIAN_OFF => write!(f, "Off"),
+            Self::KERNEL_3x3 => write!(f, "3x3"),
+            Self::KERNEL_5x5 => write!(f, "5x5"),
+            Self::KERNEL_7x7 => write!(f, "7x7"),
+        }
+    }
+}
+
+impl DepthMedianFilter {
+    pub const ALL: [Self; 4] = [
+        Self::MEDIAN_OFF,
+        Self::KERNEL_3x3,
+        Self::KERNEL_5x5,
+        Self::KERNEL_7x7,
+    ];
+}
+
+/// Post-processing filters the backend applies to the depth before streaming it.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
+pub struct DepthFilters {
+    pub spatial: bool,
+    pub temporal: bool,
+    pub speckle: bool,
+    /// Depth below this is discarded, in millimeters.
+    pub threshold_min: u16,
+    /// Depth above this is discarded, in millimeters.
+    pub threshold_max: u16,
+}
+
+impl Default for DepthFilters {
+    fn default() -> Self {
+        Self {
+            spatial: false,
+            temporal: false,
+            speckle: false,
+            threshold_min: 0,
+            threshold_max: u16::MAX,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
 pub struct DepthConfig {
     // TODO:(filip) add a legit depth config, when sdk is more defined
     pub median: DepthMedianFilter,
+    /// The stereo profile the other depth settings start from.
+    #[serde(default)]
+    pub preset: DepthProfilePreset,
     pub pointcloud: PointcloudConfig,
+    /// Camera the depth is aligned to, only `RGB` and `RIGHT` are supported.
+    /// When aligned to `RGB` the backend outputs depth at the color camera resolution.
+    #[serde(default = "DepthConfig::default_align")]
+    pub align: BoardSocket,
+    #[serde(default)]
+    pub filters: DepthFilters,
+    /// Depth resolution is divided by this before streaming, 1 = off.
+    #[serde(default = "DepthConfig::default_decimation_factor")]
+    pub decimation_factor: u8,
+    /// Stereo confidence threshold, 0-255. Lower values discard more of the less confident pixels.
+    #[serde(default = "DepthConfig::default_confidence_threshold")]
+    pub confidence_threshold: u8,
+    /// Shifts the disparity search range, in pixels. Lowers the minimum depth, at the cost of the maximum depth.
+    #[serde(default)]
+    pub disparity_shift: u16,
+    /// Also stream the colormapped raw disparity to `world/camera/disparity`.
+    #[serde(default)]
+    pub stream_disparity: bool,
+    /// Also stream the per pixel stereo confidence to `world/camera/confidence`,
+    /// as a single channel image that can be colormapped in the viewer.
+    #[serde(default)]
+    pub stream_confidence: bool,
+}
+
+impl Default for DepthConfig {
+    fn default() -> Self {
+        Self {
+            median: DepthMedianFilter::default(),
+            preset: DepthProfilePreset::default(),
+            pointcloud: PointcloudConfig::default(),
+            align: Self::default_align(),
+            filters: DepthFilters::default(),
+            decimation_factor: Self::default_decimation_factor(),
+            confidence_threshold: Self::default_confidence_threshold(),
+            disparity_shift: 0,
+            stream_disparity: false,
+            stream_confidence: false,
+        }
+    }
 }
 
 impl DepthConfig {
+    /// Shifting further leaves too little of the disparity search range for anything but the nearest objects.
+    pub const MAX_DISPARITY_SHIFT: u16 = 128;
+
+    fn default_align() -> BoardSocket {
+        BoardSocket::RIGHT
+    }
+
+    fn default_decimation_factor() -> u8 {
+        1
+    }
+
+    fn default_confidence_threshold() -> u8 {
+        230
+    }
+
+    /// Depth aligned to the color camera is logged under the color camera's pinhole,
+    /// so that backprojection uses the right intrinsics.
+    pub fn entity_path(&self) -> EntityPath {
+        match self.align {
+            BoardSocket::RGB => EntityPath::from("world/camera/image/depth"),
+            _ => EntityPath::from("world/camera/depth"),
+        }
+    }
+
     pub fn default_as_option() -> Option<Self> {
         Some(Self::default())
     }
+
+    /// The settings that shape the depth in one line, e.g. to tell why a point cloud looks off.
+    ///
+    /// Subpixel and left-right check aren't configurable on their own, they come with the preset.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("{} preset", self.preset),
+            format!("median {}", self.median),
+            format!("confidence {}", self.confidence_threshold),
+        ];
+        if self.decimation_factor > 1 {
+            parts.push(format!("decimated {}x", self.decimation_factor));
+        }
+        if self.disparity_shift > 0 {
+            parts.push(format!("disparity shift {}", self.disparity_shift));
+        }
+        let filters = [
+            ("spatial", self.filters.spatial),
+            ("temporal", self.filters.temporal),
+            ("speckle", self.filters.speckle),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect_vec();
+        if !filters.is_empty() {
+            parts.push(format!("{} filtering", filters.join(", ")));
+        }
+        parts.push(match self.align {
+            BoardSocket::RGB => "aligned to color".to_owned(),
+            _ => "aligned to right mono".to_owned(),
+        });
+        parts.join(", ")
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default, fmt::Debug)]
 pub struct PointcloudConfig {
     pub enabled: bool,
+    /// Edge length of the voxels the backend downsamples the point cloud with, 0 = off.
+    #[serde(default)]
+    pub voxel_size_m: f32,
+}
+
+impl PointcloudConfig {
+    pub const VOXEL_SIZE_RANGE_M: std::ops::RangeInclusive<f32> = 0.0..=1.0;
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
 pub struct DeviceConfig {
     pub color_camera: ColorCameraConfig,
     pub left_camera: MonoCameraConfig,
@@ -178,396 +582,3606 @@ pub struct DeviceConfig {
     #[serde(default = "DepthConfig::default_as_option")]
     pub depth: Option<DepthConfig>,
     pub ai_model: AiModel,
+    #[serde(default)]
+    pub ai_config: AiConfig,
 }
 
-#[inline]
-fn bool_true() -> bool {
-    true
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self {
+            color_camera: ColorCameraConfig::default(),
+            left_camera: MonoCameraConfig {
+                board_socket: BoardSocket::LEFT,
+                ..Default::default()
+            },
+            right_camera: MonoCameraConfig {
+                board_socket: BoardSocket::RIGHT,
+                ..Default::default()
+            },
+            depth_enabled: false,
+            depth: None,
+            ai_model: AiModel::default(),
+            ai_config: AiConfig::default(),
+        }
+    }
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
-pub struct DeviceConfigState {
-    pub config: DeviceConfig,
-    #[serde(skip)]
-    pub update_in_progress: bool,
+/// Where the calibration the device is running with comes from.
+#[derive(Clone, PartialEq, Eq, Default, fmt::Debug)]
+pub enum CalibrationSource {
+    /// The calibration stored in the device EEPROM.
+    #[default]
+    Device,
+    /// A calibration file loaded by the user, by file name. Only lasts for the session.
+    File(String),
 }
 
-impl fmt::Debug for DeviceConfig {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Device config: {:?} {:?} {:?}",
-            self.color_camera, self.left_camera, self.right_camera,
-        )
+/// Reasons a file can't be used as a DepthAI calibration.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CalibrationError {
+    #[error("Not a DepthAI calibration, it has no camera data")]
+    NoCameraData,
+    #[error("Camera entry {0} has no valid 3x3 intrinsic matrix")]
+    InvalidIntrinsics(usize),
+}
+
+/// Checks that `calibration` looks like the json a DepthAI `CalibrationHandler` writes,
+/// so we don't send the backend something it would choke on.
+pub fn validate_calibration(calibration: &serde_json::Value) -> Result<(), CalibrationError> {
+    let cameras = calibration
+        .get("cameraData")
+        .and_then(|cameras| cameras.as_array())
+        .filter(|cameras| !cameras.is_empty())
+        .ok_or(CalibrationError::NoCameraData)?;
+    for (i, camera) in cameras.iter().enumerate() {
+        // Every entry is a `[socket, camera info]` pair.
+        let is_matrix = camera
+            .get(1)
+            .and_then(|info| info.get("intrinsicMatrix"))
+            .and_then(|matrix| matrix.as_array())
+            .map_or(false, |rows| {
+                rows.len() == 3
+                    && rows.iter().all(|row| {
+                        row.as_array().map_or(false, |row| {
+                            row.len() == 3 && row.iter().all(|v| v.is_number())
+                        })
+                    })
+            });
+        if !is_matrix {
+            return Err(CalibrationError::InvalidIntrinsics(i));
+        }
     }
+    Ok(())
 }
 
-#[derive(serde::Deserialize)]
-struct PipelineResponse {
-    message: String,
+/// Reasons the backend would reject a [`DeviceConfig`].
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("Depth needs both mono cameras enabled")]
+    MonoCameraDisabled,
+
+    #[error("Depth needs both mono cameras at the same resolution")]
+    MonoResolutionMismatch,
+
+    #[error("Depth needs both mono cameras at the same FPS")]
+    MonoFpsMismatch,
+
+    #[error("Depth needs the mono cameras on two different sockets")]
+    SameStereoSockets,
 }
 
-impl Default for PipelineResponse {
-    fn default() -> Self {
-        Self {
-            message: "Pipeline not started".to_string(),
+impl DeviceConfig {
+    /// Check that the mono cameras can be used as a stereo pair.
+    pub fn validate_depth(&self) -> Result<(), ConfigError> {
+        let (left, right) = (&self.left_camera, &self.right_camera);
+        if !left.enabled || !right.enabled {
+            Err(ConfigError::MonoCameraDisabled)
+        } else if left.resolution != right.resolution {
+            Err(ConfigError::MonoResolutionMismatch)
+        } else if left.fps != right.fps {
+            Err(ConfigError::MonoFpsMismatch)
+        } else if left.board_socket == right.board_socket && left.board_socket != BoardSocket::AUTO
+        {
+            Err(ConfigError::SameStereoSockets)
+        } else {
+            Ok(())
         }
     }
-}
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
-pub enum ErrorAction {
-    None,
-    FullReset,
-}
+    /// The frame rate of the slowest enabled camera, `None` if none is enabled.
+    pub fn slowest_camera_fps(&self) -> Option<u8> {
+        [
+            (self.color_camera.enabled, self.color_camera.fps),
+            (self.left_camera.enabled, self.left_camera.fps),
+            (self.right_camera.enabled, self.right_camera.fps),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, fps)| enabled.then_some(fps))
+        .min()
+    }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
-pub struct Error {
-    pub action: ErrorAction,
-    pub message: String,
-}
+    /// Whether a pipeline with this config would stream anything at all.
+    pub fn has_streams(&self) -> bool {
+        self.color_camera.enabled || self.left_camera.enabled || self.right_camera.enabled
+    }
 
-impl Default for Error {
-    fn default() -> Self {
-        Self {
-            action: ErrorAction::None,
-            message: String::from("Invalid message"),
+    /// Configs saved before the stereo pair could be picked have no socket set.
+    pub fn resolve_mono_sockets(&mut self) {
+        if self.left_camera.board_socket == BoardSocket::AUTO {
+            self.left_camera.board_socket = BoardSocket::LEFT;
+        }
+        if self.right_camera.board_socket == BoardSocket::AUTO {
+            self.right_camera.board_socket = BoardSocket::RIGHT;
         }
     }
-}
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
-pub struct Device {
-    pub id: DeviceId,
-    // Add more fields later
-}
-impl Default for Device {
-    fn default() -> Self {
-        Self { id: "".to_string() }
+    /// The settings that differ from `previous`, one per line, e.g. `color_camera.fps: 30 → 20`.
+    pub fn changes_from(&self, previous: &DeviceConfig) -> Vec<String> {
+        fn diff(
+            path: &str,
+            old: &serde_json::Value,
+            new: &serde_json::Value,
+            changes: &mut Vec<String>,
+        ) {
+            match (old, new) {
+                (serde_json::Value::Object(old_fields), serde_json::Value::Object(new_fields)) => {
+                    for (field, new_value) in new_fields {
+                        let path = if path.is_empty() {
+                            field.clone()
+                        } else {
+                            format!("{path}.{field}")
+                        };
+                        let old_value = old_fields.get(field).unwrap_or(&serde_json::Value::Null);
+                        diff(&path, old_value, new_value, changes);
+                    }
+                }
+                _ if old != new => changes.push(format!("{path}: {old} → {new}")),
+                _ => {}
+            }
+        }
+        let mut changes = Vec::new();
+        diff(
+            "",
+            &serde_json::to_value(previous).unwrap_or_default(),
+            &serde_json::to_value(self).unwrap_or_default(),
+            &mut changes,
+        );
+        changes
+    }
+
+    /// Bring values into the ranges the device supports and settle conflicting settings,
+    /// the same way whether the config is applied or only validated.
+    pub fn normalize(&mut self) {
+        let color = &mut self.color_camera;
+        color.exposure_us = color.exposure_us.clamp(
+            *COLOR_CAMERA_EXPOSURE_RANGE_US.start(),
+            *COLOR_CAMERA_EXPOSURE_RANGE_US.end(),
+        );
+        color.iso = color.iso.clamp(
+            *COLOR_CAMERA_ISO_RANGE.start(),
+            *COLOR_CAMERA_ISO_RANGE.end(),
+        );
+        color.fps = color.fps.clamp(MIN_FPS, color.resolution.max_fps());
+        color.white_balance_k = color.white_balance_k.clamp(
+            *COLOR_CAMERA_WHITE_BALANCE_RANGE_K.start(),
+            *COLOR_CAMERA_WHITE_BALANCE_RANGE_K.end(),
+        );
+        for mono in [&mut self.left_camera, &mut self.right_camera] {
+            mono.fps = mono.fps.clamp(MIN_FPS, mono.resolution.max_fps());
+        }
+        if let Some(depth) = self.depth.as_mut() {
+            depth.pointcloud.voxel_size_m = depth.pointcloud.voxel_size_m.clamp(
+                *PointcloudConfig::VOXEL_SIZE_RANGE_M.start(),
+                *PointcloudConfig::VOXEL_SIZE_RANGE_M.end(),
+            );
+        }
+        if self.ai_config.spatial && self.depth.is_none() {
+            re_log::warn!("Spatial detections need depth, detecting in 2D only");
+            self.ai_config.spatial = false;
+        }
+        if self.left_camera.orientation != self.right_camera.orientation {
+            // Stereo depth needs both mono images in the same orientation to stay aligned.
+            re_log::warn!("Mono cameras must share the same orientation, using the left camera's");
+            self.right_camera.orientation = self.left_camera.orientation;
+        }
+        self.resolve_mono_sockets();
+    }
+
+    /// Why the backend would reject the config, see [`Self::normalize`] for what is fixed up instead.
+    pub fn check(&self) -> Result<(), String> {
+        if !self.color_camera.isp_scale_is_valid() {
+            let (numerator, denominator) = self.color_camera.isp_scale;
+            return Err(format!(
+                "Invalid ISP scale {numerator}/{denominator}, it must be greater than 0 and at most 1"
+            ));
+        }
+        if self.depth.is_some() {
+            self.validate_depth()
+                .map_err(|err| format!("Invalid device config: {err}"))?;
+        }
+        PipelineRequest::from(self)
+            .validate()
+            .map_err(|err| err.to_string())
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
-pub struct AiModel {
-    pub path: String,
-    pub display_name: String,
+/// The auto-tuner doesn't lower the frame rate any further than this.
+const MIN_BUDGET_FPS: u8 = 5;
+
+/// A config expected to stay within a bandwidth budget, see [`fit_to_bandwidth_budget`].
+pub struct BudgetSuggestion {
+    pub config: DeviceConfig,
+    /// What was lowered or disabled, in the order it was done.
+    pub actions: Vec<String>,
+    /// Expected bandwidth with the suggested config, in bytes per second.
+    pub bytes_per_sec: u64,
 }
 
-impl Default for AiModel {
-    fn default() -> Self {
-        Self {
-            path: String::from(""),
-            display_name: String::from("No model selected"),
+/// Lower `config` until the measured per channel `bandwidth` fits in `budget`, both in bytes per second.
+///
+/// Gives up the least useful things first: the extra depth streams, then color resolution
+/// through the ISP scale and finally the frame rate. Returns `None` if already within budget.
+pub fn fit_to_bandwidth_budget(
+    config: &DeviceConfig,
+    bandwidth: &HashMap<ChannelId, u64>,
+    budget: u64,
+) -> Option<BudgetSuggestion> {
+    let mut bandwidth: HashMap<ChannelId, f64> = bandwidth
+        .iter()
+        .map(|(channel, bytes_per_sec)| (*channel, *bytes_per_sec as f64))
+        .collect();
+    let over_budget =
+        |bandwidth: &HashMap<ChannelId, f64>| bandwidth.values().sum::<f64>() > budget as f64;
+    if !over_budget(&bandwidth) {
+        return None;
+    }
+    let mut config = config.clone();
+    let mut actions = Vec::new();
+
+    if let Some(depth) = config.depth.as_mut() {
+        for (channel, enabled, action) in [
+            (
+                ChannelId::PointCloud,
+                &mut depth.pointcloud.enabled,
+                "Disable the point cloud",
+            ),
+            (
+                ChannelId::Disparity,
+                &mut depth.stream_disparity,
+                "Stop streaming the disparity",
+            ),
+            (
+                ChannelId::DepthConfidence,
+                &mut depth.stream_confidence,
+                "Stop streaming the depth confidence",
+            ),
+        ] {
+            if over_budget(&bandwidth) && *enabled {
+                *enabled = false;
+                bandwidth.remove(&channel);
+                actions.push(action.to_owned());
+            }
+        }
+    }
+
+    // Halving the scale quarters the pixels, stop at a quarter of the sensor resolution.
+    while over_budget(&bandwidth) && config.color_camera.enabled {
+        let (numerator, denominator) = config.color_camera.isp_scale;
+        if u16::from(numerator) * 4 <= u16::from(denominator) || denominator > 8 {
+            break;
+        }
+        config.color_camera.isp_scale = (numerator, denominator * 2);
+        if let Some(bytes_per_sec) = bandwidth.get_mut(&ChannelId::ColorImage) {
+            *bytes_per_sec /= 4.0;
+        }
+        actions.push(format!(
+            "Lower the color camera ISP scale to {numerator}/{}",
+            denominator * 2
+        ));
+    }
+
+    while over_budget(&bandwidth) {
+        let fps = config
+            .color_camera
+            .fps
+            .max(config.left_camera.fps)
+            .max(config.right_camera.fps);
+        if fps <= MIN_BUDGET_FPS {
+            break;
         }
+        let new_fps = (fps / 2).max(MIN_BUDGET_FPS);
+        let mut scale_fps = |camera_fps: &mut u8, channels: &[ChannelId]| {
+            if *camera_fps > new_fps {
+                let ratio = new_fps as f64 / *camera_fps as f64;
+                for channel in channels {
+                    if let Some(bytes_per_sec) = bandwidth.get_mut(channel) {
+                        *bytes_per_sec *= ratio;
+                    }
+                }
+                *camera_fps = new_fps;
+            }
+        };
+        scale_fps(&mut config.color_camera.fps, &[ChannelId::ColorImage]);
+        // Everything computed from the stereo pair runs at the mono camera rate.
+        let stereo_channels = [
+            ChannelId::DepthImage,
+            ChannelId::PointCloud,
+            ChannelId::Disparity,
+            ChannelId::DepthConfidence,
+        ];
+        scale_fps(
+            &mut config.left_camera.fps,
+            &[ChannelId::LeftMono, ChannelId::LeftRectified],
+        );
+        let mut right_channels = vec![ChannelId::RightMono, ChannelId::RightRectified];
+        right_channels.extend(stereo_channels);
+        scale_fps(&mut config.right_camera.fps, &right_channels);
+        actions.push(format!("Lower the camera FPS to {new_fps}"));
     }
+
+    Some(BudgetSuggestion {
+        config,
+        actions,
+        bytes_per_sec: bandwidth.values().sum::<f64>() as u64,
+    })
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
-pub struct State {
+#[inline]
+fn bool_true() -> bool {
+    true
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct DeviceConfigState {
+    pub config: DeviceConfig,
     #[serde(skip)]
-    devices_available: Option<Vec<DeviceId>>,
+    pub update_in_progress: bool,
+    /// Names of the presets saved on disk, see [`Self::refresh_presets`].
     #[serde(skip)]
-    pub selected_device: Device,
-    pub device_config: DeviceConfigState,
-
-    #[serde(skip, default = "all_subscriptions")]
-    // Want to resubscribe to api when app is reloaded
-    pub subscriptions: Vec<ChannelId>, // Shown in ui
+    pub presets: Vec<String>,
     #[serde(skip)]
-    setting_subscriptions: bool,
+    pub selected_preset: String,
+    /// Name typed into the "Save as…" popup.
     #[serde(skip)]
-    pub backend_comms: BackendCommChannel,
+    pub new_preset_name: String,
     #[serde(skip)]
-    poll_instant: Option<Instant>,
-    #[serde(default = "default_neural_networks")]
-    pub neural_networks: Vec<AiModel>,
+    pub history: ConfigHistory,
+    /// Hold changes back until they're applied, so they can be validated without restarting the pipeline.
+    #[serde(default)]
+    pub validate_before_apply: bool,
+    /// Changes that are yet to be applied, see [`Self::validate_before_apply`].
+    #[serde(skip)]
+    pub staged: Option<DeviceConfig>,
+    /// What the backend thought of the staged config, `None` until it was validated.
+    #[serde(skip)]
+    pub validation: Option<ConfigValidation>,
+    #[serde(skip)]
+    pub validation_in_progress: bool,
+    /// Sent along with the last validation request, the backend's reply carries it back.
+    #[serde(skip)]
+    pub validation_request_id: u32,
+    /// The config the backend last confirmed the pipeline runs with.
+    #[serde(skip)]
+    pub running: Option<DeviceConfig>,
 }
 
-// Kind of dangerous, IMPORTANT: Make sure all ChannelId variants are covered
-fn all_subscriptions() -> Vec<ChannelId> {
-    vec![
-        ChannelId::ColorImage,
-        ChannelId::LeftMono,
-        ChannelId::RightMono,
-        ChannelId::DepthImage,
-        ChannelId::PointCloud,
-        ChannelId::ImuData,
-    ]
+impl DeviceConfigState {
+    /// Whether the backend confirmed that the pipeline runs with `config`.
+    pub fn is_applied(&self, config: &DeviceConfig) -> bool {
+        self.running.as_ref() == Some(config)
+    }
+
+    /// Whether `config` was sent and the backend is yet to confirm it.
+    pub fn is_pending(&self, config: &DeviceConfig) -> bool {
+        self.update_in_progress && self.config == *config
+    }
+
+    /// Hold `config` back until it's applied, any earlier validation no longer holds.
+    pub fn stage(&mut self, config: DeviceConfig) {
+        self.staged = (!self.is_applied(&config)).then_some(config);
+        self.validation = None;
+        self.validation_in_progress = false;
+    }
 }
 
-fn default_neural_networks() -> Vec<AiModel> {
-    vec![
-        AiModel::default(),
-        AiModel {
-            path: String::from("yolo-v3-tiny-tf"),
-            display_name: String::from("Yolo (tiny)"),
-        },
-        AiModel {
-            path: String::from("mobilenet-ssd"),
-            display_name: String::from("MobileNet SSD"),
-        },
-        AiModel {
-            path: String::from("face-detection-retail-0004"),
-            display_name: String::from("Face Detection"),
-        },
-        AiModel {
-            path: String::from("age-gender-recognition-retail-0013"),
-            display_name: String::from("Age gender recognition"),
-        },
-    ]
+/// The backend's verdict on a config it was asked to validate, without restarting the pipeline.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Default, fmt::Debug)]
+pub struct ConfigValidation {
+    /// The request this is the reply to, see [`DeviceConfigState::validation_request_id`].
+    #[serde(default)]
+    pub request_id: u32,
+    /// The pipeline would start, but e.g. drop frames or run hot.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// The pipeline would fail to start.
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
-impl Default for State {
-    fn default() -> Self {
-        Self {
-            devices_available: None,
-            selected_device: Device::default(),
-            device_config: DeviceConfigState::default(),
-            subscriptions: all_subscriptions(),
-            setting_subscriptions: false,
-            backend_comms: BackendCommChannel::default(),
-            poll_instant: Some(Instant::now()), // No default for Instant
-            neural_networks: default_neural_networks(),
-        }
+impl ConfigValidation {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
     }
 }
 
-#[repr(u8)]
-#[derive(serde::Serialize, serde::Deserialize, Copy, Clone, PartialEq, Eq, fmt::Debug, Hash)]
-pub enum ChannelId {
-    ColorImage,
-    LeftMono,
-    RightMono,
-    DepthImage,
-    PointCloud,
-    PinholeCamera,
-    ImuData,
+/// How many applied configs are kept for undo.
+const MAX_CONFIG_HISTORY: usize = 20;
+
+/// A config that was sent to the backend, see [`ConfigHistory`].
+pub struct ConfigHistoryEntry {
+    pub config: DeviceConfig,
+    /// Whether the backend started a pipeline with it.
+    pub started: bool,
 }
 
-use lazy_static::lazy_static;
-lazy_static! {
-    static ref DEPTHAI_ENTITY_HASHES: HashMap<EntityPathHash, ChannelId> = HashMap::from([
-        (
-            EntityPath::from("world/camera/image/rgb").hash(),
-            ChannelId::ColorImage,
-        ),
-        (
-            EntityPath::from("Left mono camera").hash(),
-            ChannelId::LeftMono,
-        ),
-        (
-            EntityPath::from("Right mono camera").hash(),
-            ChannelId::RightMono,
-        ),
-        (
-            EntityPath::from("right mono camera/depth").hash(),
-            ChannelId::DepthImage,
-        ),
-        (
-            EntityPath::from("world/point_cloud").hash(),
-            ChannelId::PointCloud,
-        ),
-    ]);
+/// The configs that were applied this session, to step back to one that worked.
+#[derive(Default)]
+pub struct ConfigHistory {
+    /// Oldest first.
+    entries: std::collections::VecDeque<ConfigHistoryEntry>,
+    /// Index of the entry that was applied last, entries after it were undone.
+    current: usize,
 }
 
-impl State {
-    pub fn entities_to_remove(&mut self, entity_path: &BTreeSet<EntityPath>) -> Vec<EntityPath> {
-        let mut remove_channels = Vec::<ChannelId>::new();
-        if let Some(depth) = self.device_config.config.depth {
-            if !depth.pointcloud.enabled {
-                remove_channels.push(ChannelId::PointCloud);
-            }
-        } else {
-            remove_channels.push(ChannelId::DepthImage);
+impl ConfigHistory {
+    /// Record an applied config. Anything that was undone is dropped, like in an editor.
+    ///
+    /// Applying what [`Self::undo_config`] or [`Self::redo_config`] returned moves through it instead.
+    pub fn push(&mut self, config: &DeviceConfig) {
+        let is_entry = |i: usize| {
+            self.entries
+                .get(i)
+                .map_or(false, |entry| entry.config == *config)
+        };
+        if is_entry(self.current) {
+            // Re-applying the current entry, e.g. a retry.
+            return;
+        }
+        if self.current > 0 && is_entry(self.current - 1) {
+            self.current -= 1;
+            return;
         }
+        if is_entry(self.current + 1) {
+            self.current += 1;
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.current + 1);
+        }
+        self.entries.push_back(ConfigHistoryEntry {
+            config: config.clone(),
+            started: false,
+        });
+        if self.entries.len() > MAX_CONFIG_HISTORY {
+            self.entries.pop_front();
+        }
+        self.current = self.entries.len() - 1;
+    }
 
-        entity_path
+    /// The pipeline started with the config that was applied last.
+    pub fn mark_started(&mut self) {
+        if let Some(entry) = self.entries.get_mut(self.current) {
+            entry.started = true;
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current + 1 < self.entries.len()
+    }
+
+    /// The config to apply to step back, the history only moves once it's applied.
+    pub fn undo_config(&self) -> Option<&DeviceConfig> {
+        let previous = self.current.checked_sub(1)?;
+        self.entries.get(previous).map(|entry| &entry.config)
+    }
+
+    /// The config to apply to step forward again after an undo.
+    pub fn redo_config(&self) -> Option<&DeviceConfig> {
+        self.entries
+            .get(self.current + 1)
+            .map(|entry| &entry.config)
+    }
+
+    /// Oldest first, with whether each entry is the one applied last.
+    pub fn iter(&self) -> impl Iterator<Item = (&ConfigHistoryEntry, bool)> {
+        self.entries
             .iter()
-            .filter_map(|ep| {
-                if let Some(channel) = DEPTHAI_ENTITY_HASHES.get(&ep.hash()) {
-                    if remove_channels.contains(channel) {
-                        return Some(ep.clone());
-                    }
-                }
-                None
-            })
-            .collect_vec()
+            .enumerate()
+            .map(|(i, entry)| (entry, i == self.current))
     }
+}
 
-    pub fn set_subscriptions_from_space_views(&mut self, visible_space_views: Vec<&SpaceView>) {
-        // If any bool in the vec is true, the channel is currently visible in the ui somewhere
-        let mut visibilities = HashMap::<ChannelId, Vec<bool>>::from([
-            (ChannelId::ColorImage, Vec::new()),
-            (ChannelId::LeftMono, Vec::new()),
-            (ChannelId::RightMono, Vec::new()),
-            (ChannelId::DepthImage, Vec::new()),
-            (ChannelId::PointCloud, Vec::new()),
-        ]);
-        // Fill in visibilities
-        for space_view in visible_space_views.iter() {
-            let mut property_map = space_view.data_blueprint.data_blueprints_projected();
-            for entity_path in space_view.data_blueprint.entity_paths().iter() {
-                if let Some(channel_id) = DEPTHAI_ENTITY_HASHES.get(&entity_path.hash()) {
-                    if let Some(visibility) = visibilities.get_mut(channel_id) {
-                        visibility.push(property_map.get(entity_path).visible);
-                    }
-                }
-            }
-        }
+#[cfg(not(target_arch = "wasm32"))]
+impl DeviceConfigState {
+    /// Presets are stored as one json file per preset in the app data dir.
+    fn presets_dir() -> Option<std::path::PathBuf> {
+        directories_next::ProjectDirs::from("", "", "rerun")
+            .map(|dirs| dirs.data_dir().join("depthai_presets"))
+    }
 
-        // First add subscriptions that are always possible in terms of ui (no enable/disable buttons for these)
-        let mut possible_subscriptions = Vec::<ChannelId>::from([
-            ChannelId::ColorImage,
-            ChannelId::LeftMono,
-            ChannelId::RightMono,
-            ChannelId::ImuData,
-        ]);
-        // Now add non default subscriptions
-        if self.device_config.config.depth.is_some() {
-            possible_subscriptions.push(ChannelId::DepthImage);
-            if let Some(depth) = self.device_config.config.depth {
-                if depth.pointcloud.enabled {
-                    possible_subscriptions.push(ChannelId::PointCloud);
-                }
-            }
+    fn preset_path(name: &str) -> Option<std::path::PathBuf> {
+        let is_valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_');
+        if !is_valid_name {
+            re_log::error!(
+                "Invalid preset name {name:?}, only letters, digits, spaces, '-' and '_' are allowed"
+            );
+            return None;
         }
+        Self::presets_dir().map(|dir| dir.join(format!("{name}.json")))
+    }
 
-        // Filter visibilities, include those that are currently visible and also possible (example pointcloud enabled == pointcloud possible)
-        let mut subscriptions = visibilities
-            .iter()
-            .filter_map(|(channel, vis)| {
-                if vis.iter().any(|x| *x) {
-                    if possible_subscriptions.contains(channel) {
-                        return Some(*channel);
-                    }
-                }
-                None
+    pub fn refresh_presets(&mut self) {
+        self.presets = Self::presets_dir()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let path = entry.ok()?.path();
+                        if path.extension()? != "json" {
+                            return None;
+                        }
+                        Some(path.file_stem()?.to_string_lossy().to_string())
+                    })
+                    .sorted()
+                    .collect()
             })
-            .collect_vec();
+            .unwrap_or_default();
+    }
 
-        // Keep subscriptions that should be visible but have not yet been sent by the backend
-        for channel in all_subscriptions() {
-            if !subscriptions.contains(&channel)
-                && possible_subscriptions.contains(&channel)
-                && self.subscriptions.contains(&channel)
-            {
-                subscriptions.push(channel);
+    /// Save the current config under `name`, overwriting any preset with the same name.
+    pub fn save_preset(&mut self, name: &str) {
+        let Some(path) = Self::preset_path(name) else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|err| err.to_string())
+            .and_then(|_| serde_json::to_string_pretty(&self.config).map_err(|err| err.to_string()))
+            .and_then(|json| std::fs::write(&path, json).map_err(|err| err.to_string()));
+        match result {
+            Ok(()) => {
+                re_log::info!("Saved preset {name:?}");
+                self.selected_preset = name.to_owned();
+                self.refresh_presets();
             }
+            Err(err) => re_log::error!("Failed to save preset {name:?}: {err}"),
         }
-
-        self.set_subscriptions(&subscriptions);
     }
 
-    pub fn set_subscriptions(&mut self, subscriptions: &Vec<ChannelId>) {
-        if self.subscriptions.len() == subscriptions.len()
-            && self
-                .subscriptions
-                .iter()
-                .all(|channel_id| subscriptions.contains(channel_id))
-        {
-            return;
+    pub fn read_preset(name: &str) -> Option<DeviceConfig> {
+        let path = Self::preset_path(name)?;
+        let result = std::fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()));
+        match result {
+            Ok(config) => Some(config),
+            Err(err) => {
+                re_log::error!("Failed to load preset {name:?}: {err}");
+                None
+            }
         }
-        self.backend_comms.set_subscriptions(subscriptions);
-        self.subscriptions = subscriptions.clone();
     }
 
-    pub fn get_devices(&mut self) -> Vec<DeviceId> {
-        // Return stored available devices or fetch them from the api (they get fetched every 30s via poller)
-        if let Some(devices) = self.devices_available.clone() {
-            return devices;
+    pub fn delete_preset(&mut self, name: &str) {
+        let Some(path) = Self::preset_path(name) else {
+            return;
+        };
+        if let Err(err) = std::fs::remove_file(path) {
+            re_log::error!("Failed to delete preset {name:?}: {err}");
         }
-        Vec::new()
+        if self.selected_preset == name {
+            self.selected_preset.clear();
+        }
+        self.refresh_presets();
     }
+}
 
-    pub fn shutdown(&mut self) {
-        self.backend_comms.shutdown();
+impl fmt::Debug for DeviceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Device config: {:?} {:?} {:?}",
+            self.color_camera, self.left_camera, self.right_camera,
+        )
     }
+}
 
-    pub fn update(&mut self) {
-        if let Some(ws_message) = self.backend_comms.receive() {
-            re_log::debug!("Received message: {:?}", ws_message);
-            match ws_message.data {
-                WsMessageData::Subscriptions(subscriptions) => {
-                    re_log::debug!("Setting subscriptions");
-                    self.subscriptions = subscriptions;
-                }
-                WsMessageData::Devices(devices) => {
-                    re_log::debug!("Setting devices...");
-                    self.devices_available = Some(devices);
-                }
-                WsMessageData::Pipeline(config) => {
-                    let mut subs = self.subscriptions.clone();
-                    if let Some(depth) = config.depth {
-                        subs.push(ChannelId::DepthImage);
-                        if depth.pointcloud.enabled {
-                            subs.push(ChannelId::PointCloud);
-                        }
-                    }
-                    self.device_config.config = config;
-                    self.device_config.config.depth_enabled =
-                        self.device_config.config.depth.is_some();
-                    self.set_subscriptions(&subs);
-                    self.device_config.update_in_progress = false;
-                }
-                WsMessageData::Device(device) => {
-                    re_log::debug!("Setting device");
-                    self.selected_device = device;
-                    self.backend_comms.set_subscriptions(&self.subscriptions);
-                    self.backend_comms.set_pipeline(&self.device_config.config);
-                    self.device_config.update_in_progress = true;
-                }
-                WsMessageData::Error(error) => {
-                    re_log::error!("Error: {:?}", error.message);
-                    self.device_config.update_in_progress = false;
-                    match error.action {
-                        ErrorAction::None => (),
-                        ErrorAction::FullReset => {
-                            self.set_device("".into());
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
+#[derive(serde::Deserialize)]
+struct PipelineResponse {
+    message: String,
+}
 
-        if let Some(poll_instant) = self.poll_instant {
-            if poll_instant.elapsed().as_secs() < 2 {
-                return;
-            }
-            if self.selected_device.id == "" {
-                self.backend_comms.get_devices();
-            }
-            self.poll_instant = Some(Instant::now());
-        } else {
-            self.poll_instant = Some(Instant::now());
+impl Default for PipelineResponse {
+    fn default() -> Self {
+        Self {
+            message: "Pipeline not started".to_string(),
         }
     }
+}
 
-    pub fn set_device(&mut self, device_id: DeviceId) {
-        if self.selected_device.id == device_id {
-            return;
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
+pub enum ErrorAction {
+    None,
+    FullReset,
+}
+
+/// Why the backend failed, so the viewer can react without matching on the message.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default, fmt::Debug)]
+pub enum ErrorKind {
+    /// Anything the viewer has no special handling for, including kinds from newer backends.
+    #[default]
+    #[serde(other)]
+    Unknown,
+    /// Another process, e.g. a second viewer or a DepthAI script, has the device open.
+    #[serde(alias = "DeviceInUse")]
+    DeviceBusy,
+    /// The device rejected the pipeline built from the config.
+    PipelineInvalid,
+    /// The device has no stereo calibration, which depth and aligned streams need.
+    CalibrationMissing,
+    /// The connection to the device dropped, e.g. a loose cable or a PoE device going offline.
+    Network,
+}
+
+impl ErrorKind {
+    /// What to tell the user instead of the raw backend message, if anything.
+    pub fn explanation(&self) -> Option<&'static str> {
+        match self {
+            Self::Unknown => None,
+            Self::DeviceBusy => Some(
+                "The device is already in use by another program. \
+                Close other viewers or DepthAI scripts using it, or force release it and retry.",
+            ),
+            Self::PipelineInvalid => Some(
+                "The device rejected the configuration. \
+                Try a lower resolution or FPS, or fewer streams.",
+            ),
+            Self::CalibrationMissing => Some(
+                "The device isn't calibrated, so depth isn't available. \
+                Calibrate the device or turn depth off.",
+            ),
+            Self::Network => {
+                Some("Lost the connection to the device. Check its cable or network connection.")
+            }
         }
-        re_log::debug!("Setting device: {:?}", device_id);
-        self.backend_comms.set_device(device_id);
     }
+}
 
-    pub fn set_device_config(&mut self, config: &mut DeviceConfig) {
-        if !self
-            .backend_comms
-            .ws
-            .connected
-            .load(std::sync::atomic::Ordering::SeqCst)
-            || self.selected_device.id == ""
-        {
-            return;
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
+pub struct Error {
+    pub action: ErrorAction,
+    pub message: String,
+    /// Set by the backend for errors that may go away when retrying, e.g. a busy device.
+    #[serde(default)]
+    pub transient: bool,
+    /// Older backends sent this as `code`.
+    #[serde(default, alias = "code")]
+    pub kind: ErrorKind,
+}
+
+impl Default for Error {
+    fn default() -> Self {
+        Self {
+            action: ErrorAction::None,
+            message: String::from("Invalid message"),
+            transient: false,
+            kind: ErrorKind::Unknown,
         }
-        config.left_camera.board_socket = BoardSocket::LEFT;
-        config.right_camera.board_socket = BoardSocket::RIGHT;
-        self.device_config.config = config.clone();
-        self.backend_comms.set_pipeline(&self.device_config.config);
-        re_log::info!("Creating pipeline...");
-        self.device_config.update_in_progress = true;
     }
 }
 
-pub type DeviceId = String; // i64 because of serialization
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
+pub struct Device {
+    pub id: DeviceId,
+    /// Product name, e.g. `OAK-D`, `OAK-D-Lite` or `OAK-1`, empty if the backend didn't report it.
+    #[serde(default)]
+    pub model: String,
+    /// Color camera resolutions the device can stream, empty if the backend didn't report them.
+    #[serde(default)]
+    pub supported_color_resolutions: Vec<ColorCameraResolution>,
+    // Add more fields later
+}
+impl Default for Device {
+    fn default() -> Self {
+        Self {
+            id: "".to_string(),
+            model: String::new(),
+            supported_color_resolutions: Vec::new(),
+        }
+    }
+}
+
+/// Periodically reported by the backend for the selected device.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Default, fmt::Debug)]
+pub struct DeviceStats {
+    pub temperature_c: f32,
+    /// 0-1
+    pub cpu_usage: f32,
+    /// 0-1
+    pub mem_usage: f32,
+    /// Frames the backend dropped per stream since the pipeline started, because it couldn't keep up.
+    #[serde(default)]
+    pub dropped_frames: HashMap<ChannelId, u64>,
+    /// Approximate number of points per point cloud frame after downsampling.
+    #[serde(default)]
+    pub approximate_point_count: Option<u32>,
+}
+
+/// What a camera's auto exposure settled on.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default, fmt::Debug)]
+pub struct CameraExposure {
+    pub exposure_us: u32,
+    pub iso: u32,
+    /// Only reported for color cameras.
+    #[serde(default)]
+    pub white_balance_k: Option<u16>,
+}
+
+/// Periodically reported by the backend, per camera, for cameras that are streaming.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default, fmt::Debug)]
+pub struct ExposureReadback {
+    #[serde(default)]
+    pub color: Option<CameraExposure>,
+    #[serde(default)]
+    pub left: Option<CameraExposure>,
+    #[serde(default)]
+    pub right: Option<CameraExposure>,
+}
+
+/// Tracks how far apart the timestamps of the latest frames of the subscribed streams are.
+///
+/// Streams that are overlaid, like color and depth, show ghosting when their frames drift apart.
+#[derive(Default)]
+pub struct FrameSync {
+    /// Log time of the latest frame of every stream in nanoseconds, and when it arrived.
+    latest: HashMap<ChannelId, (i64, Instant)>,
+    /// When each skew was measured and the skew in seconds, oldest first.
+    skews: std::collections::VecDeque<(Instant, f32)>,
+}
+
+impl FrameSync {
+    /// The skew is reported over this rolling window, streams that stopped for longer are ignored.
+    pub const WINDOW_SECS: f32 = 5.0;
+    /// How much further than a frame apart the streams can drift, see [`Self::warning_secs`].
+    pub const WARNING_MARGIN_SECS: f32 = 0.02;
+
+    /// Above this the streams are likely to be visibly out of sync.
+    ///
+    /// Streams only line up to within a frame of the slowest of them, which runs at `fps`.
+    pub fn warning_secs(fps: u8) -> f32 {
+        1.0 / f32::from(fps.max(MIN_FPS)) + Self::WARNING_MARGIN_SECS
+    }
+
+    /// Call for every frame received on `channel`.
+    pub fn on_frame(
+        &mut self,
+        channel: ChannelId,
+        timestamp_ns: i64,
+        subscriptions: &[ChannelId],
+        now: Instant,
+    ) {
+        // The IMU isn't a frame stream and the preview runs at its own, lower rate.
+        if matches!(channel, ChannelId::ImuData | ChannelId::ColorPreview) {
+            return;
+        }
+        self.latest.insert(channel, (timestamp_ns, now));
+
+        let window = std::time::Duration::from_secs_f32(Self::WINDOW_SECS);
+        let timestamps = self
+            .latest
+            .iter()
+            .filter(|(channel, (_, arrived))| {
+                subscriptions.contains(channel) && now.duration_since(*arrived) <= window
+            })
+            .map(|(_, (timestamp_ns, _))| *timestamp_ns)
+            .collect_vec();
+        if timestamps.len() >= 2 {
+            if let itertools::MinMaxResult::MinMax(min, max) = timestamps.iter().minmax() {
+                self.skews.push_back((now, (max - min) as f32 / 1e9));
+            }
+        }
+        while self.skews.front().map_or(false, |(measured, _)| {
+            now.duration_since(*measured) > window
+        }) {
+            self.skews.pop_front();
+        }
+    }
+
+    /// The largest skew between the subscribed streams over the last [`Self::WINDOW_SECS`],
+    /// `None` while fewer than two of them are streaming.
+    pub fn max_skew_secs(&self, now: Instant) -> Option<f32> {
+        let window = std::time::Duration::from_secs_f32(Self::WINDOW_SECS);
+        self.skews
+            .iter()
+            .filter(|(measured, _)| now.duration_since(*measured) <= window)
+            .map(|(_, skew)| *skew)
+            .reduce(f32::max)
+    }
+
+    pub fn reset(&mut self) {
+        self.latest.clear();
+        self.skews.clear();
+    }
+}
+
+/// Drops frames on the host to stay under an FPS cap, independent of the sensor FPS.
+#[derive(Default)]
+pub struct FrameThrottle {
+    /// Timestamp from which the next frame of every stream is kept, in nanoseconds.
+    next_due: HashMap<ChannelId, i64>,
+}
+
+impl FrameThrottle {
+    /// Whether to keep the frame of `channel` logged at `timestamp_ns`, given the cap in frames per second.
+    pub fn accept(&mut self, channel: ChannelId, timestamp_ns: i64, fps_cap: u8) -> bool {
+        let period_ns = 1_000_000_000 / i64::from(fps_cap.max(1));
+        let next_due = self.next_due.entry(channel).or_insert(timestamp_ns);
+        if timestamp_ns < *next_due {
+            return false;
+        }
+        // Stepping by the period keeps the average rate at the cap despite jitter,
+        // unless the stream stalled, then start over from this frame.
+        *next_due = if timestamp_ns - *next_due >= period_ns {
+            timestamp_ns + period_ns
+        } else {
+            *next_due + period_ns
+        };
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.next_due.clear();
+    }
+}
+
+/// A log message from the backend, e.g. pipeline warnings or device events.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Default, fmt::Debug)]
+pub struct LogEntry {
+    /// One of `CRITICAL`, `ERROR`, `WARN`, `INFO`, `DEBUG` or `TRACE`.
+    pub level: String,
+    pub message: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: f64,
+}
+
+/// Version of the viewer <-> backend websocket api this viewer speaks.
+pub const API_VERSION: u32 = 1;
+
+/// Exchanged when the websocket opens, the viewer sends its version and the backend replies with its own.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Default, fmt::Debug)]
+pub struct Hello {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub viewer_version: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub backend_version: String,
+    #[serde(default)]
+    pub api_version: u32,
+}
+
+impl Hello {
+    pub fn is_compatible(&self) -> bool {
+        self.api_version == API_VERSION
+    }
+}
+
+impl DeviceStats {
+    /// Above this the device starts throttling.
+    pub const HIGH_TEMPERATURE_C: f32 = 90.0;
+}
+
+/// The device temperatures of the last [`Self::WINDOW_SECS`], to warn about sustained heat
+/// rather than a single hot reading.
+pub struct TemperatureHistory {
+    start: Instant,
+    history: egui::util::History<f32>,
+}
+
+impl Default for TemperatureHistory {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            history: egui::util::History::new(0..1000, Self::WINDOW_SECS),
+        }
+    }
+}
+
+impl TemperatureHistory {
+    pub const WINDOW_SECS: f32 = 30.0;
+
+    pub fn add(&mut self, temperature_c: f32) {
+        let now = self.start.elapsed().as_secs_f64();
+        self.history.add(now, temperature_c);
+        self.history.flush(now);
+    }
+
+    pub fn average_c(&self) -> Option<f32> {
+        self.history.average()
+    }
+
+    pub fn max_c(&self) -> Option<f32> {
+        self.history.values().reduce(f32::max)
+    }
+
+    /// Whether the device has been running hot on average, see [`DeviceStats::HIGH_TEMPERATURE_C`].
+    pub fn is_overheating(&self) -> bool {
+        self.average_c()
+            .map_or(false, |average| average >= DeviceStats::HIGH_TEMPERATURE_C)
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Device {
+    pub fn supports(&self, config: &DeviceConfig) -> bool {
+        self.supports_color_resolution(config.color_camera.resolution)
+            && (self.has_stereo() || config.depth.is_none())
+    }
+
+    pub fn supports_color_resolution(&self, resolution: ColorCameraResolution) -> bool {
+        self.supported_color_resolutions.is_empty()
+            || self.supported_color_resolutions.contains(&resolution)
+    }
+
+    /// Whether the device has the mono camera pair needed for depth, the OAK-1 family only has a color camera.
+    pub fn has_stereo(&self) -> bool {
+        !self.model.starts_with("OAK-1")
+    }
+}
+
+impl DeviceConfig {
+    /// Leave out what `device` can't do, so the pipeline doesn't fail on it.
+    pub fn limit_to(&mut self, device: &Device) {
+        if !device.supports_color_resolution(self.color_camera.resolution) {
+            if let Some(resolution) = device.supported_color_resolutions.first() {
+                self.color_camera.resolution = *resolution;
+            }
+        }
+        if !device.has_stereo() {
+            self.left_camera.enabled = false;
+            self.right_camera.enabled = false;
+            self.depth = None;
+            self.depth_enabled = false;
+        }
+    }
+
+    /// A config that puts less load on the device, to bring its temperature down.
+    ///
+    /// Halves the frame rates, drops the color camera to its lowest resolution
+    /// and turns off the depth post processing filters.
+    pub fn reduced_load(&self) -> Self {
+        let mut config = self.clone();
+        for fps in [
+            &mut config.color_camera.fps,
+            &mut config.left_camera.fps,
+            &mut config.right_camera.fps,
+        ] {
+            *fps = (*fps / 2).max(MIN_FPS);
+        }
+        config.color_camera.resolution = ColorCameraResolution::THE_1080_P;
+        if let Some(depth) = config.depth.as_mut() {
+            depth.filters.spatial = false;
+            depth.filters.temporal = false;
+            depth.filters.speckle = false;
+        }
+        config
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, fmt::Debug)]
+pub struct AiModel {
+    pub path: String,
+    pub display_name: String,
+}
+
+impl Default for AiModel {
+    fn default() -> Self {
+        Self {
+            path: String::from(""),
+            display_name: String::from("No model selected"),
+        }
+    }
+}
+
+impl AiModel {
+    /// Built-in models come with the viewer and can't be removed from the list.
+    pub fn is_builtin(&self) -> bool {
+        default_neural_networks().contains(self)
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, fmt::Debug)]
+pub struct AiConfig {
+    /// Detections below this confidence (0..1) are discarded on the device.
+    pub confidence_threshold: f32,
+    /// Locate the detections in 3D using the depth map, needs depth to be enabled.
+    #[serde(default)]
+    pub spatial: bool,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            confidence_threshold: 0.5,
+            spatial: false,
+        }
+    }
+}
+
+/// A section of the device configuration ui, used to reveal it from other views.
+#[derive(Clone, Copy, PartialEq, Eq, fmt::Debug)]
+pub enum ConfigSection {
+    ColorCamera,
+    LeftCamera,
+    RightCamera,
+    Depth,
+    AiModel,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct State {
+    #[serde(skip)]
+    devices_available: Option<Vec<DeviceId>>,
+    #[serde(skip)]
+    pub selected_device: Device,
+    /// The device that was selected before the app was reloaded, reconnected to if it's still available.
+    #[serde(default)]
+    last_selected_device: DeviceId,
+    pub device_config: DeviceConfigState,
+
+    /// Persisted so a reload doesn't re-enable the streams the user had dropped.
+    #[serde(default = "all_subscriptions")]
+    pub subscriptions: Vec<ChannelId>, // Shown in ui
+    /// Set while waiting for the backend to confirm subscriptions we changed.
+    #[serde(skip)]
+    setting_subscriptions: bool,
+    /// Whether to tell the user once the backend confirmed the subscriptions we changed.
+    #[serde(skip)]
+    notify_subscriptions_set: bool,
+    /// While streaming is paused, the subscriptions to restore when it's resumed.
+    #[serde(skip)]
+    paused_subscriptions: Option<Vec<ChannelId>>,
+    /// Channels the user stopped streaming, these stay unsubscribed even when visible in a view.
+    #[serde(default)]
+    unsubscribed_channels: Vec<ChannelId>,
+    /// Only stream the channels that are visible in a space view, otherwise stream everything configured.
+    #[serde(default = "bool_true")]
+    pub only_subscribe_to_visible: bool,
+    /// Stream the low resolution color preview, only wanted while framing the scene.
+    #[serde(default)]
+    pub show_color_preview: bool,
+    #[serde(skip)]
+    pub backend_comms: BackendCommChannel,
+    #[serde(skip)]
+    poll_instant: Option<Instant>,
+    /// Whether the viewer window was focused last frame, polling is paused while it isn't.
+    #[serde(skip)]
+    window_focused: bool,
+    #[serde(default = "default_neural_networks")]
+    pub neural_networks: Vec<AiModel>,
+    /// Names the user gave the device streams, shown instead of the entity paths.
+    #[serde(default)]
+    pub channel_display_names: HashMap<ChannelId, String>,
+    /// Set when a section of the device configuration should be revealed, e.g. from the node graph.
+    #[serde(skip)]
+    pub focused_config_section: Option<ConfigSection>,
+    /// Set while waiting for the backend to log a full resolution still to `world/camera/still`.
+    #[serde(skip)]
+    pub capture_in_progress: bool,
+    /// Whether the IMU is shown in any visible space view, in which case it stays subscribed.
+    #[serde(skip)]
+    pub imu_visible: bool,
+    /// Which device streams each space view showed, per device. The entity paths are the same
+    /// for every device, so without this the visibility is lost when switching between them.
+    #[serde(default)]
+    entity_visibility: HashMap<DeviceId, HashMap<(SpaceViewId, EntityPath), bool>>,
+    /// Set when a device was selected and its remembered visibility is yet to be applied.
+    #[serde(skip)]
+    restore_entity_visibility: bool,
+    /// The color map last picked for each single channel stream, applied wherever the stream is shown.
+    #[serde(default = "default_channel_color_mappers")]
+    pub channel_color_mappers: HashMap<ChannelId, ColorMapper>,
+    /// The color map each view showed each stream with last frame, `None` for the automatic one.
+    #[serde(skip)]
+    shown_color_mappers: HashMap<(SpaceViewId, EntityPath), Option<ColorMapper>>,
+    /// Also log every IMU axis as a scalar, see [`imu_scalar_rows`].
+    #[serde(default)]
+    pub log_imu_scalars: bool,
+    /// Frames above this rate are dropped before they're logged, the sensors keep running at their own FPS.
+    #[serde(default)]
+    pub host_fps_cap: Option<u8>,
+    #[serde(skip)]
+    frame_throttle: FrameThrottle,
+    /// Set while the color crop region is being drawn on the color camera view.
+    #[serde(skip)]
+    pub drawing_crop: bool,
+    /// Where the crop drag started and where it is now, relative to the streamed color frame.
+    #[serde(skip)]
+    pub crop_drag: Option<(egui::Pos2, egui::Pos2)>,
+    /// The neural network and its confidence threshold each device last ran with,
+    /// restored when the device is selected again.
+    #[serde(default)]
+    device_ai_models: HashMap<DeviceId, (AiModel, f32)>,
+    /// When the last pipeline was requested, used to give up on a backend that doesn't respond.
+    #[serde(skip)]
+    pipeline_request_instant: Option<Instant>,
+    /// The last error reported by the backend, shown in the ui until dismissed or a pipeline starts.
+    #[serde(skip)]
+    pub last_error: Option<Error>,
+    /// How many times the current pipeline has been requested, see [`MAX_PIPELINE_ATTEMPTS`].
+    #[serde(skip)]
+    pipeline_attempts: u8,
+    /// Set when a failed pipeline should be requested again.
+    #[serde(skip)]
+    pipeline_retry_instant: Option<Instant>,
+    #[serde(skip)]
+    pub device_stats: Option<DeviceStats>,
+    #[serde(skip)]
+    pub temperature_history: TemperatureHistory,
+    /// Latest exposure the cameras settled on, `None` until the backend reports it.
+    #[serde(skip)]
+    pub exposure_readback: Option<ExposureReadback>,
+    #[serde(skip)]
+    pub frame_sync: FrameSync,
+    /// Received bytes per second of every stream, as measured by the bandwidth panel.
+    #[serde(skip)]
+    pub channel_bandwidth: HashMap<ChannelId, u64>,
+    /// The most recent backend log messages, oldest first.
+    #[serde(skip)]
+    pub backend_logs: std::collections::VecDeque<LogEntry>,
+    /// Whether the websocket was connected last frame, used to greet the backend on (re)connect.
+    #[serde(skip)]
+    backend_connected: bool,
+    /// What the backend replied to our hello, `None` until it has.
+    #[serde(skip)]
+    pub backend_hello: Option<Hello>,
+    #[serde(skip)]
+    pub calibration_source: CalibrationSource,
+    /// Set while reviewing a recording instead of live data, nothing is sent to the device then.
+    #[serde(skip)]
+    pub playback: bool,
+    /// Set when the user asked to open a recording, handled by the app.
+    #[serde(skip)]
+    pub load_recording_requested: bool,
+    /// Set while the incoming streams are being written to an `.rrd` file.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pub recording: Option<super::recording::StreamRecording>,
+}
+
+/// How many backend log messages to keep around for the ui.
+const MAX_BACKEND_LOGS: usize = 1000;
+
+/// How long to wait for the backend to start a pipeline before giving up.
+const PIPELINE_TIMEOUT_SECS: u64 = 10;
+/// How many times a pipeline is requested before giving up on transient errors.
+const MAX_PIPELINE_ATTEMPTS: u8 = 3;
+const PIPELINE_RETRY_DELAY_SECS: u64 = 1;
+
+// Kind of dangerous, IMPORTANT: Make sure all ChannelId variants are covered
+fn all_subscriptions() -> Vec<ChannelId> {
+    vec![
+        ChannelId::ColorImage,
+        ChannelId::LeftMono,
+        ChannelId::RightMono,
+        ChannelId::DepthImage,
+        ChannelId::PointCloud,
+        ChannelId::ImuData,
+        ChannelId::Detections,
+        ChannelId::Disparity,
+        ChannelId::DepthConfidence,
+        ChannelId::SpatialDetections,
+        ChannelId::ColorPreview,
+        ChannelId::LeftRectified,
+        ChannelId::RightRectified,
+    ]
+}
+
+fn default_channel_color_mappers() -> HashMap<ChannelId, ColorMapper> {
+    HashMap::from([
+        (
+            ChannelId::DepthImage,
+            ColorMapper::Colormap(Colormap::Turbo),
+        ),
+        (
+            ChannelId::DepthConfidence,
+            ColorMapper::Colormap(Colormap::Viridis),
+        ),
+    ])
+}
+
+fn default_neural_networks() -> Vec<AiModel> {
+    vec![
+        AiModel::default(),
+        AiModel {
+            path: String::from("yolo-v3-tiny-tf"),
+            display_name: String::from("Yolo (tiny)"),
+        },
+        AiModel {
+            path: String::from("mobilenet-ssd"),
+            display_name: String::from("MobileNet SSD"),
+        },
+        AiModel {
+            path: String::from("face-detection-retail-0004"),
+            display_name: String::from("Face Detection"),
+        },
+        AiModel {
+            path: String::from("age-gender-recognition-retail-0013"),
+            display_name: String::from("Age gender recognition"),
+        },
+    ]
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            devices_available: None,
+            selected_device: Device::default(),
+            last_selected_device: DeviceId::default(),
+            device_config: DeviceConfigState::default(),
+            subscriptions: all_subscriptions(),
+            setting_subscriptions: false,
+            notify_subscriptions_set: false,
+            paused_subscriptions: None,
+            unsubscribed_channels: Vec::new(),
+            only_subscribe_to_visible: true,
+            show_color_preview: false,
+            backend_comms: BackendCommChannel::default(),
+            poll_instant: Some(Instant::now()), // No default for Instant
+            window_focused: true,
+            neural_networks: default_neural_networks(),
+            channel_display_names: HashMap::new(),
+            focused_config_section: None,
+            capture_in_progress: false,
+            imu_visible: false,
+            entity_visibility: HashMap::new(),
+            restore_entity_visibility: false,
+            channel_color_mappers: default_channel_color_mappers(),
+            shown_color_mappers: HashMap::new(),
+            log_imu_scalars: false,
+            host_fps_cap: None,
+            frame_throttle: FrameThrottle::default(),
+            drawing_crop: false,
+            crop_drag: None,
+            device_ai_models: HashMap::new(),
+            pipeline_request_instant: None,
+            last_error: None,
+            pipeline_attempts: 0,
+            pipeline_retry_instant: None,
+            device_stats: None,
+            temperature_history: TemperatureHistory::default(),
+            exposure_readback: None,
+            frame_sync: FrameSync::default(),
+            channel_bandwidth: HashMap::new(),
+            backend_logs: Default::default(),
+            backend_connected: false,
+            backend_hello: None,
+            calibration_source: CalibrationSource::default(),
+            playback: false,
+            load_recording_requested: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            recording: None,
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(serde::Serialize, serde::Deserialize, Copy, Clone, PartialEq, Eq, fmt::Debug, Hash)]
+pub enum ChannelId {
+    ColorImage,
+    LeftMono,
+    RightMono,
+    DepthImage,
+    PointCloud,
+    PinholeCamera,
+    ImuData,
+    Detections,
+    Disparity,
+    DepthConfidence,
+    SpatialDetections,
+    /// A low resolution, low FPS copy of the color stream for framing the scene.
+    ColorPreview,
+    /// The mono images after rectification, as the stereo matching sees them.
+    LeftRectified,
+    RightRectified,
+}
+
+impl ChannelId {
+    /// The part of the device configuration that produces this stream.
+    pub fn config_section(self) -> Option<ConfigSection> {
+        match self {
+            Self::ColorImage | Self::ColorPreview => Some(ConfigSection::ColorCamera),
+            Self::LeftMono | Self::LeftRectified => Some(ConfigSection::LeftCamera),
+            Self::RightMono | Self::RightRectified => Some(ConfigSection::RightCamera),
+            Self::DepthImage | Self::PointCloud | Self::Disparity | Self::DepthConfidence => {
+                Some(ConfigSection::Depth)
+            }
+            Self::Detections | Self::SpatialDetections => Some(ConfigSection::AiModel),
+            Self::PinholeCamera | Self::ImuData => None,
+        }
+    }
+}
+
+use lazy_static::lazy_static;
+lazy_static! {
+    // Everything the backend logs lives under `world/camera`, so the streams share transforms.
+    // Only what's in the color camera's image space (the color frames, the depth aligned to it and
+    // the detections) is under its pinhole at `world/camera/image`.
+    static ref DEPTHAI_ENTITY_HASHES: HashMap<EntityPathHash, ChannelId> = HashMap::from([
+        (
+            EntityPath::from(COLOR_IMAGE_ENTITY_PATH).hash(),
+            ChannelId::ColorImage,
+        ),
+        (
+            EntityPath::from("world/camera/left").hash(),
+            ChannelId::LeftMono,
+        ),
+        (
+            EntityPath::from("world/camera/right").hash(),
+            ChannelId::RightMono,
+        ),
+        (
+            EntityPath::from("world/camera/depth").hash(),
+            ChannelId::DepthImage,
+        ),
+        (
+            EntityPath::from("world/camera/image/depth").hash(),
+            ChannelId::DepthImage,
+        ),
+        (
+            EntityPath::from("world/camera/disparity").hash(),
+            ChannelId::Disparity,
+        ),
+        (
+            EntityPath::from("world/camera/confidence").hash(),
+            ChannelId::DepthConfidence,
+        ),
+        (
+            EntityPath::from("world/point_cloud").hash(),
+            ChannelId::PointCloud,
+        ),
+        (
+            EntityPath::from("world/camera/image/detections").hash(),
+            ChannelId::Detections,
+        ),
+        // In camera space, so the boxes end up in front of the camera in the 3D view.
+        (
+            EntityPath::from("world/camera/spatial_detections").hash(),
+            ChannelId::SpatialDetections,
+        ),
+        // Its own space, it doesn't match the color camera intrinsics.
+        (
+            EntityPath::from("preview/color").hash(),
+            ChannelId::ColorPreview,
+        ),
+        (
+            EntityPath::from("world/camera/left_rectified").hash(),
+            ChannelId::LeftRectified,
+        ),
+        (
+            EntityPath::from("world/camera/right_rectified").hash(),
+            ChannelId::RightRectified,
+        ),
+        (ImuData::entity_path().hash(), ChannelId::ImuData),
+    ]);
+}
+
+/// The device stream that logs to the given entity, if any.
+pub fn channel_from_entity_path(entity_path: &EntityPath) -> Option<ChannelId> {
+    DEPTHAI_ENTITY_HASHES.get(&entity_path.hash()).copied()
+}
+
+/// Splits the IMU samples of `row` into one scalar per axis, logged under e.g. `imu_data/accel/x`,
+/// so they can be plotted in time series views next to the rest of the data.
+pub fn imu_scalar_rows(row: &re_log_types::DataRow) -> Vec<re_log_types::DataRow> {
+    use re_log_types::{component_types::Scalar, Component as _, DataRow, RowId};
+
+    if row.entity_path() != &ImuData::entity_path() {
+        return Vec::new();
+    }
+    let Some(index) = row.find_cell(&ImuData::name()) else {
+        return Vec::new();
+    };
+    let mut rows = Vec::new();
+    for imu_data in row.cells()[index].to_native::<ImuData>() {
+        let sensors = [
+            ("accel", Some(imu_data.accel)),
+            ("gyro", Some(imu_data.gyro)),
+            ("mag", imu_data.mag),
+        ];
+        for (sensor, values) in sensors {
+            let Some(values) = values else {
+                continue;
+            };
+            for (axis, value) in [("x", values.x), ("y", values.y), ("z", values.z)] {
+                rows.push(DataRow::from_cells1(
+                    RowId::random(),
+                    format!("{}/{sensor}/{axis}", ImuData::entity_path()).as_str(),
+                    row.timepoint().clone(),
+                    1,
+                    vec![Scalar(value as f64)],
+                ));
+            }
+        }
+    }
+    rows
+}
+
+/// Reads only the entity path column of the message, leaving the (potentially large) data columns alone.
+pub fn arrow_msg_entity_paths(arrow_msg: &re_log_types::ArrowMsg) -> Vec<EntityPath> {
+    use re_log_types::external::arrow2_convert::deserialize::TryIntoCollection;
+
+    let Some(index) = arrow_msg
+        .schema
+        .fields
+        .iter()
+        .position(|field| field.name == re_log_types::COLUMN_ENTITY_PATH) else {
+        return Vec::new();
+    };
+    arrow_msg
+        .chunk
+        .arrays()
+        .get(index)
+        .and_then(|array| TryIntoCollection::try_into_collection(array.as_ref()).ok())
+        .unwrap_or_default()
+}
+
+/// Like [`channel_from_entity_path`], but also accepts entities namespaced by device.
+///
+/// When several devices stream at once, each device's entities are logged under its id,
+/// e.g. `<device id>/world/point_cloud`, while the selected device may log directly under the root.
+pub fn device_channel_from_entity_path(
+    entity_path: &EntityPath,
+) -> Option<(Option<DeviceId>, ChannelId)> {
+    if let Some(channel) = channel_from_entity_path(entity_path) {
+        return Some((None, channel));
+    }
+    let (device, rest) = entity_path.as_slice().split_first()?;
+    let channel = channel_from_entity_path(&EntityPath::from(rest))?;
+    Some((Some(device.to_string()), channel))
+}
+
+/// The label drawn on a detection's bounding box.
+///
+/// Models don't always name their classes, detections are labeled anyway so they can be told apart.
+pub fn detection_label(label: Option<String>, class_id: Option<u16>) -> String {
+    label.unwrap_or_else(|| match class_id {
+        Some(class_id) => format!("Class {class_id}"),
+        None => "Detection".to_owned(),
+    })
+}
+
+/// Whether both hold the same channels, regardless of their order.
+fn same_channels(a: &[ChannelId], b: &[ChannelId]) -> bool {
+    a.len() == b.len() && a.iter().all(|channel| b.contains(channel))
+}
+
+/// Channels whose entities should be removed from the views, either because the pipeline no longer
+/// produces them or because we're no longer subscribed to them.
+fn channels_to_remove(config: &DeviceConfig, subscriptions: &[ChannelId]) -> Vec<ChannelId> {
+    let mut remove_channels = Vec::<ChannelId>::new();
+    if let Some(depth) = config.depth {
+        if !depth.pointcloud.enabled {
+            remove_channels.push(ChannelId::PointCloud);
+        }
+        if !depth.stream_disparity {
+            remove_channels.push(ChannelId::Disparity);
+        }
+        if !depth.stream_confidence {
+            remove_channels.push(ChannelId::DepthConfidence);
+        }
+    } else {
+        remove_channels.push(ChannelId::DepthImage);
+        remove_channels.push(ChannelId::Disparity);
+        remove_channels.push(ChannelId::DepthConfidence);
+        remove_channels.push(ChannelId::LeftRectified);
+        remove_channels.push(ChannelId::RightRectified);
+    }
+    if config.ai_model.path.is_empty() {
+        remove_channels.push(ChannelId::Detections);
+    }
+    if config.ai_model.path.is_empty() || !config.ai_config.spatial || config.depth.is_none() {
+        remove_channels.push(ChannelId::SpatialDetections);
+    }
+    for (channel, enabled) in [
+        (ChannelId::ColorImage, config.color_camera.enabled),
+        (ChannelId::ColorPreview, config.color_camera.enabled),
+        (ChannelId::LeftMono, config.left_camera.enabled),
+        (ChannelId::RightMono, config.right_camera.enabled),
+    ] {
+        if !enabled || !subscriptions.contains(&channel) {
+            remove_channels.push(channel);
+        }
+    }
+    remove_channels
+}
+
+impl State {
+    /// The device that logs to this entity, if it's one of the device streams.
+    pub fn device_for_entity(&self, entity_path: &EntityPath) -> Option<DeviceId> {
+        match device_channel_from_entity_path(entity_path)? {
+            (Some(device), _) => Some(device),
+            (None, _) => {
+                (!self.selected_device.id.is_empty()).then(|| self.selected_device.id.clone())
+            }
+        }
+    }
+
+    /// The selected device's color camera entity, if the space view rooted at `space` shows it.
+    ///
+    /// Like the other device streams it may be namespaced by device, see [`device_channel_from_entity_path`].
+    pub fn color_image_entity_path(&self, space: &EntityPath) -> Option<EntityPath> {
+        let name = EntityPath::from(COLOR_IMAGE_ENTITY_PATH).last()?.clone();
+        let entity_path = space.join(&EntityPath::new(vec![name]));
+        let (_, channel) = device_channel_from_entity_path(&entity_path)?;
+        (channel == ChannelId::ColorImage
+            && self.device_for_entity(&entity_path).as_ref() == Some(&self.selected_device.id))
+        .then_some(entity_path)
+    }
+
+    /// The name the user gave the stream that logs to this entity, if any.
+    pub fn entity_display_name(&self, entity_path: &EntityPath) -> Option<&str> {
+        self.channel_display_name(channel_from_entity_path(entity_path)?)
+    }
+
+    /// The name the user gave the stream a space view shows, unless it shows differently named streams.
+    ///
+    /// Space views are rooted above the entities they show, e.g. `world/camera/image` for the color
+    /// camera at `world/camera/image/rgb`, so the name is looked up from the shown entities.
+    pub fn space_view_display_name<'a>(
+        &self,
+        entity_paths: impl IntoIterator<Item = &'a EntityPath>,
+    ) -> Option<&str> {
+        let mut names = entity_paths.into_iter().filter_map(|entity_path| {
+            let (_, channel) = device_channel_from_entity_path(entity_path)?;
+            self.channel_display_name(channel)
+        });
+        let name = names.next()?;
+        names.all(|other| other == name).then_some(name)
+    }
+
+    /// The color map the user last picked for the stream that logs to this entity, if any.
+    pub fn entity_color_mapper(&self, entity_path: &EntityPath) -> Option<ColorMapper> {
+        let (_, channel) = device_channel_from_entity_path(entity_path)?;
+        self.channel_color_mappers.get(&channel).copied()
+    }
+
+    fn channel_display_name(&self, channel: ChannelId) -> Option<&str> {
+        self.channel_display_names
+            .get(&channel)
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+    }
+
+    pub fn entities_to_remove(&mut self, entity_path: &BTreeSet<EntityPath>) -> Vec<EntityPath> {
+        let remove_channels =
+            channels_to_remove(&self.device_config.config, &self.requested_subscriptions());
+
+        entity_path
+            .iter()
+            .filter_map(|ep| {
+                if let Some(channel) = DEPTHAI_ENTITY_HASHES.get(&ep.hash()) {
+                    if remove_channels.contains(channel) {
+                        return Some(ep.clone());
+                    }
+                }
+                None
+            })
+            .collect_vec()
+    }
+
+    /// Remembers which streams of the selected device are visible in each space view,
+    /// or restores them right after the device was (re)selected.
+    pub fn sync_entity_visibility<'a>(
+        &mut self,
+        space_views: impl Iterator<Item = &'a mut SpaceView>,
+    ) {
+        if !is_device_id(&self.selected_device.id) {
+            return;
+        }
+        let restore = std::mem::take(&mut self.restore_entity_visibility);
+        let remembered = self
+            .entity_visibility
+            .entry(self.selected_device.id.clone())
+            .or_default();
+        for space_view in space_views {
+            let entity_paths = space_view
+                .data_blueprint
+                .entity_paths()
+                .iter()
+                .filter(|entity_path| {
+                    device_channel_from_entity_path(entity_path).map_or(false, |(device, _)| {
+                        device.map_or(true, |device| device == self.selected_device.id)
+                    })
+                })
+                .cloned()
+                .collect_vec();
+            let properties_map = space_view.data_blueprint.data_blueprints_individual();
+            for entity_path in entity_paths {
+                let mut properties = properties_map.get(&entity_path);
+                let key = (space_view.id, entity_path);
+                if restore {
+                    if let Some(visible) = remembered.get(&key) {
+                        properties.visible = *visible;
+                        properties_map.set(key.1, properties);
+                    }
+                } else {
+                    remembered.insert(key, properties.visible);
+                }
+            }
+        }
+    }
+
+    /// Applies the remembered color map of each stream to the entities that still use the automatic one,
+    /// and remembers the color maps the user picked.
+    pub fn sync_color_mappers<'a>(&mut self, space_views: impl Iterator<Item = &'a mut SpaceView>) {
+        for space_view in space_views {
+            let channels = space_view
+                .data_blueprint
+                .entity_paths()
+                .iter()
+                .filter_map(|entity_path| {
+                    device_channel_from_entity_path(entity_path)
+                        .map(|(_, channel)| (entity_path.clone(), channel))
+                })
+                .collect_vec();
+            let properties_map = space_view.data_blueprint.data_blueprints_individual();
+            for (entity_path, channel) in channels {
+                let mut properties = properties_map.get(&entity_path);
+                let key = (space_view.id, entity_path.clone());
+                match properties.color_mapper {
+                    EditableAutoValue::UserEdited(color_mapper) => {
+                        // Only a color map that was just picked is remembered, views that show
+                        // the same stream differently would take turns overriding it otherwise.
+                        let shown = self.shown_color_mappers.insert(key, Some(color_mapper));
+                        if shown.map_or(false, |shown| shown != Some(color_mapper)) {
+                            self.channel_color_mappers.insert(channel, color_mapper);
+                        }
+                    }
+                    EditableAutoValue::Auto(_) => {
+                        let color_mapper = self.channel_color_mappers.get(&channel).copied();
+                        if let Some(color_mapper) = color_mapper {
+                            properties.color_mapper = EditableAutoValue::UserEdited(color_mapper);
+                            properties_map.set(entity_path, properties);
+                        }
+                        self.shown_color_mappers.insert(key, color_mapper);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The streams of each device that are visible in the given space views.
+    ///
+    /// Entities that aren't namespaced by device are the selected device's.
+    fn visible_channels_per_device(
+        &self,
+        space_views: &[&SpaceView],
+    ) -> HashMap<DeviceId, Vec<ChannelId>> {
+        let mut visible_channels = HashMap::<DeviceId, Vec<ChannelId>>::new();
+        for space_view in space_views {
+            let property_map = space_view.data_blueprint.data_blueprints_projected();
+            for entity_path in space_view.data_blueprint.entity_paths() {
+                let Some((device, channel)) = device_channel_from_entity_path(entity_path) else {
+                    continue;
+                };
+                if !property_map.get(entity_path).visible {
+                    continue;
+                }
+                let device = device.unwrap_or_else(|| self.selected_device.id.clone());
+                let channels = visible_channels.entry(device).or_default();
+                if !channels.contains(&channel) {
+                    channels.push(channel);
+                }
+            }
+        }
+        visible_channels
+    }
+
+    /// The devices whose streams the given space views show.
+    pub fn devices_in_space_views<'a>(
+        &self,
+        space_views: impl IntoIterator<Item = &'a SpaceView>,
+    ) -> BTreeSet<DeviceId> {
+        space_views
+            .into_iter()
+            .flat_map(|space_view| space_view.data_blueprint.entity_paths())
+            .filter_map(|entity_path| self.device_for_entity(entity_path))
+            .collect()
+    }
+
+    pub fn set_subscriptions_from_space_views(&mut self, visible_space_views: Vec<&SpaceView>) {
+        // Only the selected device streams to us, what's visible of the other devices doesn't matter.
+        let visible_channels = self
+            .visible_channels_per_device(&visible_space_views)
+            .remove(&self.selected_device.id)
+            .unwrap_or_default();
+
+        self.imu_visible = visible_channels.contains(&ChannelId::ImuData);
+
+        // First add subscriptions that are always possible in terms of ui (no enable/disable buttons for these)
+        // The IMU is always part of the pipeline, so it follows view visibility like the cameras do.
+        let config = &self.device_config.config;
+        let mut possible_subscriptions = [
+            (ChannelId::ColorImage, config.color_camera.enabled),
+            (
+                ChannelId::ColorPreview,
+                config.color_camera.enabled && self.show_color_preview,
+            ),
+            (ChannelId::LeftMono, config.left_camera.enabled),
+            (ChannelId::RightMono, config.right_camera.enabled),
+            (ChannelId::ImuData, true),
+        ]
+        .into_iter()
+        .filter_map(|(channel, enabled)| enabled.then_some(channel))
+        .collect_vec();
+        // Now add non default subscriptions
+        if self.device_config.config.depth.is_some() {
+            possible_subscriptions.push(ChannelId::DepthImage);
+            possible_subscriptions.push(ChannelId::LeftRectified);
+            possible_subscriptions.push(ChannelId::RightRectified);
+            if let Some(depth) = self.device_config.config.depth {
+                if depth.pointcloud.enabled {
+                    possible_subscriptions.push(ChannelId::PointCloud);
+                }
+                if depth.stream_disparity {
+                    possible_subscriptions.push(ChannelId::Disparity);
+                }
+                if depth.stream_confidence {
+                    possible_subscriptions.push(ChannelId::DepthConfidence);
+                }
+            }
+        }
+        if !self.device_config.config.ai_model.path.is_empty() {
+            possible_subscriptions.push(ChannelId::Detections);
+            if self.device_config.config.ai_config.spatial
+                && self.device_config.config.depth.is_some()
+            {
+                possible_subscriptions.push(ChannelId::SpatialDetections);
+            }
+        }
+        possible_subscriptions.retain(|channel| !self.unsubscribed_channels.contains(channel));
+
+        if !self.only_subscribe_to_visible {
+            self.set_subscriptions(&possible_subscriptions);
+            return;
+        }
+
+        // Include those that are currently visible and also possible (example pointcloud enabled == pointcloud possible)
+        let mut subscriptions = visible_channels
+            .into_iter()
+            .filter(|channel| possible_subscriptions.contains(channel))
+            .collect_vec();
+
+        // Keep subscriptions that should be visible but have not yet been sent by the backend
+        for channel in all_subscriptions() {
+            if !subscriptions.contains(&channel)
+                && possible_subscriptions.contains(&channel)
+                && self.subscriptions.contains(&channel)
+            {
+                subscriptions.push(channel);
+            }
+        }
+
+        self.set_subscriptions(&subscriptions);
+    }
+
+    pub fn set_subscriptions(&mut self, subscriptions: &Vec<ChannelId>) {
+        if self.playback {
+            return;
+        }
+        if let Some(paused_subscriptions) = &mut self.paused_subscriptions {
+            // Applied once streaming is resumed.
+            *paused_subscriptions = subscriptions.clone();
+            return;
+        }
+        if same_channels(&self.subscriptions, subscriptions) {
+            return;
+        }
+        self.backend_comms.set_subscriptions(subscriptions);
+        self.subscriptions = subscriptions.clone();
+        self.setting_subscriptions = true;
+        self.notify_subscriptions_set = true;
+    }
+
+    /// Start or stop streaming the given channel.
+    pub fn toggle_subscription(&mut self, channel: ChannelId) {
+        let mut subscriptions = self.requested_subscriptions();
+        if subscriptions.contains(&channel) {
+            subscriptions.retain(|c| *c != channel);
+            self.unsubscribed_channels.push(channel);
+        } else {
+            subscriptions.push(channel);
+            self.unsubscribed_channels.retain(|c| *c != channel);
+        }
+        self.set_subscriptions(&subscriptions);
+    }
+
+    /// What we want to stream, even while streaming is paused.
+    fn requested_subscriptions(&self) -> Vec<ChannelId> {
+        self.paused_subscriptions
+            .clone()
+            .unwrap_or_else(|| self.subscriptions.clone())
+    }
+
+    pub fn is_streaming_paused(&self) -> bool {
+        self.paused_subscriptions.is_some()
+    }
+
+    /// Stop all streams without touching the pipeline, the device stays configured.
+    pub fn pause_streaming(&mut self) {
+        if self.playback || self.is_streaming_paused() {
+            return;
+        }
+        let subscriptions = self.subscriptions.clone();
+        self.set_subscriptions(&Vec::new());
+        self.paused_subscriptions = Some(subscriptions);
+    }
+
+    /// Stream again what was subscribed before [`Self::pause_streaming`].
+    pub fn resume_streaming(&mut self) {
+        if let Some(subscriptions) = self.paused_subscriptions.take() {
+            self.set_subscriptions(&subscriptions);
+        }
+    }
+
+    /// While paused, get the next frame of every channel that was streaming before the pause.
+    pub fn step_frame(&mut self) {
+        if self.playback {
+            return;
+        }
+        if let Some(subscriptions) = &self.paused_subscriptions {
+            self.backend_comms.request_frame(subscriptions);
+        }
+    }
+
+    pub fn get_devices(&mut self) -> Vec<DeviceId> {
+        // Return stored available devices or fetch them from the api (they get fetched every 30s via poller)
+        if let Some(devices) = self.devices_available.clone() {
+            return devices;
+        }
+        Vec::new()
+    }
+
+    pub fn shutdown(&mut self) {
+        // Abandon anything still in flight, nobody is going to wait for the responses.
+        self.device_config.update_in_progress = false;
+        self.pipeline_request_instant = None;
+        self.pipeline_retry_instant = None;
+        self.capture_in_progress = false;
+        // Stop the backend from streaming to a viewer that's gone.
+        self.backend_comms.set_subscriptions(&Vec::new());
+        self.backend_comms.shutdown();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.stop_recording();
+    }
+
+    pub fn is_backend_connected(&self) -> bool {
+        self.backend_connected
+    }
+
+    /// Start over with a new connection to the backend, e.g. after it was restarted.
+    pub fn reconnect_backend(&mut self) {
+        self.backend_comms.ws.reconnect();
+        self.backend_connected = false;
+        self.backend_hello = None;
+    }
+
+    /// Switch between reviewing a recording and showing the live device data.
+    pub fn set_playback(&mut self, playback: bool) {
+        if self.playback == playback {
+            return;
+        }
+        re_log::debug!("Playback: {playback}");
+        self.playback = playback;
+        // Nothing was sent to the backend during playback, it may be streaming something else by now.
+        if !playback && self.backend_connected {
+            self.restart_streaming();
+        }
+    }
+
+    /// Ask the backend for our subscriptions and pipeline again, when it may have lost track of them.
+    fn restart_streaming(&mut self) {
+        if self.playback
+            || !should_start_pipeline(&self.selected_device.id, &self.device_config.config)
+        {
+            return;
+        }
+        self.backend_comms.set_subscriptions(&self.subscriptions);
+        self.setting_subscriptions = true;
+        self.pipeline_attempts = 0;
+        self.request_pipeline();
+    }
+
+    /// Start writing the incoming streams to `path`.
+    ///
+    /// `begin_msg` is the [`re_log_types::LogMsg::BeginRecordingMsg`] of the recording the streams go to.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start_recording(
+        &mut self,
+        path: std::path::PathBuf,
+        begin_msg: Option<re_log_types::LogMsg>,
+    ) {
+        match super::recording::StreamRecording::new(path, begin_msg) {
+            Ok(recording) => {
+                re_log::info!("Recording to {:?}", recording.path);
+                self.recording = Some(recording);
+            }
+            Err(err) => re_log::error!("Can't start recording: {err}"),
+        }
+    }
+
+    /// Stops the recording, if any, finishing the file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stop_recording(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            re_log::info!("Saved recording to {:?}", recording.path);
+        }
+    }
+
+    /// Call for every received message, written to the file while recording.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn record(&self, msg: &re_log_types::LogMsg) {
+        if let Some(recording) = &self.recording {
+            recording.record(msg);
+        }
+    }
+
+    /// The IMU samples of `msg` as per axis scalars, see [`imu_scalar_rows`].
+    ///
+    /// `None` unless [`Self::log_imu_scalars`] is set and `msg` holds IMU data.
+    pub fn imu_scalar_msg(&self, msg: &re_log_types::LogMsg) -> Option<re_log_types::LogMsg> {
+        use re_log_types::{DataTable, LogMsg, TableId};
+
+        if !self.log_imu_scalars {
+            return None;
+        }
+        let LogMsg::ArrowMsg(recording_id, arrow_msg) = msg else {
+            return None;
+        };
+        // Don't deserialize the (potentially large) images just to find out they aren't IMU data.
+        if !arrow_msg_entity_paths(arrow_msg).contains(&ImuData::entity_path()) {
+            return None;
+        }
+        let rows = DataTable::from_arrow_msg(arrow_msg)
+            .map_err(|err| re_log::warn_once!("Failed to read the IMU data: {err}"))
+            .ok()?
+            .to_rows()
+            .flat_map(|row| imu_scalar_rows(&row))
+            .collect_vec();
+        if rows.is_empty() {
+            return None;
+        }
+        let arrow_msg = DataTable::from_rows(TableId::random(), rows)
+            .to_arrow_msg()
+            .map_err(|err| re_log::warn_once!("Failed to log the IMU data as scalars: {err}"))
+            .ok()?;
+        Some(LogMsg::ArrowMsg(*recording_id, arrow_msg))
+    }
+
+    /// Whether `msg` should be dropped to stay under [`Self::host_fps_cap`].
+    ///
+    /// Only messages that hold nothing but camera frames are dropped, the IMU and transforms always go through.
+    pub fn throttle(&mut self, msg: &re_log_types::LogMsg) -> bool {
+        let Some(fps_cap) = self.host_fps_cap else {
+            return false;
+        };
+        let re_log_types::LogMsg::ArrowMsg(_, arrow_msg) = msg else {
+            return false;
+        };
+        let Some(log_time) = arrow_msg
+            .timepoint_max
+            .get(&re_log_types::Timeline::log_time()) else {
+            return false;
+        };
+        let channels = arrow_msg_entity_paths(arrow_msg)
+            .iter()
+            .map(channel_from_entity_path)
+            .unique()
+            .collect_vec();
+        let is_frame = |channel: &Option<ChannelId>| {
+            !matches!(
+                channel,
+                None | Some(ChannelId::ImuData | ChannelId::PinholeCamera)
+            )
+        };
+        if channels.is_empty() || !channels.iter().all(is_frame) {
+            return false;
+        }
+        !channels
+            .into_iter()
+            .flatten()
+            .fold(true, |accept, channel| {
+                self.frame_throttle
+                    .accept(channel, log_time.as_i64(), fps_cap)
+                    && accept
+            })
+    }
+
+    /// Call for every received message, keeps track of the frame timestamps for [`FrameSync`].
+    pub fn on_log_msg(&mut self, msg: &re_log_types::LogMsg) {
+        let re_log_types::LogMsg::ArrowMsg(_, arrow_msg) = msg else {
+            return;
+        };
+        let Some(log_time) = arrow_msg
+            .timepoint_max
+            .get(&re_log_types::Timeline::log_time()) else {
+            return;
+        };
+        let now = Instant::now();
+        for channel in arrow_msg_entity_paths(arrow_msg)
+            .iter()
+            .filter_map(channel_from_entity_path)
+            .unique()
+        {
+            self.frame_sync
+                .on_frame(channel, log_time.as_i64(), &self.subscriptions, now);
+        }
+    }
+
+    /// Adds a user provided `.blob` or `.json` model to the selectable neural networks.
+    pub fn add_custom_neural_network(&mut self, path: &std::path::Path) {
+        if !path.is_file() {
+            re_log::error!("Can't load model, {:?} is not a file", path);
+            return;
+        }
+        if !matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("blob" | "json")
+        ) {
+            re_log::error!("Can't load model, {:?} is not a .blob or .json file", path);
+            return;
+        }
+        let model = AiModel {
+            path: path.to_string_lossy().to_string(),
+            display_name: path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().to_string()),
+        };
+        if !self.neural_networks.contains(&model) {
+            self.neural_networks.push(model);
+        }
+    }
+
+    /// Overrides the device calibration with a DepthAI calibration json for this session.
+    pub fn load_calibration(&mut self, path: &std::path::Path) {
+        let calibration = match std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<serde_json::Value>(&json).map_err(|err| err.to_string())
+            }) {
+            Ok(calibration) => calibration,
+            Err(err) => {
+                re_log::error!("Can't load calibration from {:?}: {err}", path);
+                return;
+            }
+        };
+        if let Err(err) = validate_calibration(&calibration) {
+            re_log::error!("Can't load calibration from {:?}: {err}", path);
+            return;
+        }
+        self.backend_comms.set_calibration(calibration);
+        self.calibration_source = CalibrationSource::File(
+            path.file_name()
+                .map_or_else(String::new, |name| name.to_string_lossy().to_string()),
+        );
+    }
+
+    /// Goes back to the calibration stored on the device.
+    pub fn reset_calibration(&mut self) {
+        self.backend_comms.set_calibration(serde_json::Value::Null);
+        self.calibration_source = CalibrationSource::Device;
+    }
+
+    /// Removes a user added model, built-in models are kept.
+    pub fn remove_neural_network(&mut self, index: usize) {
+        if self
+            .neural_networks
+            .get(index)
+            .map_or(false, |model| !model.is_builtin())
+        {
+            self.neural_networks.remove(index);
+        }
+    }
+
+    /// Call once per frame. `poll_interval_secs` is how often to look for devices while none is selected.
+    pub fn update(&mut self, poll_interval_secs: f32, window_focused: bool) {
+        let connected = self
+            .backend_comms
+            .ws
+            .connected
+            .load(std::sync::atomic::Ordering::SeqCst);
+        if connected && !self.backend_connected {
+            self.backend_comms.hello();
+            // After a reconnect the backend may have lost what it was streaming, ask for it again.
+            self.restart_streaming();
+        } else if !connected {
+            self.backend_hello = None;
+        }
+        self.backend_connected = connected;
+
+        if let Some(ws_message) = self.backend_comms.receive() {
+            re_log::debug!("Received message: {:?}", ws_message);
+            match ws_message.data {
+                WsMessageData::Subscriptions(subscriptions) => {
+                    re_log::debug!("Setting subscriptions");
+                    // Ours are kept either way, they're persisted across reloads. A reply to them
+                    // may leave out what can't be streamed right now, anything else is what the
+                    // backend was asked for before, e.g. by the previous session, so it's corrected.
+                    if std::mem::take(&mut self.setting_subscriptions) {
+                        if std::mem::take(&mut self.notify_subscriptions_set) {
+                            re_log::info!("Subscriptions updated");
+                        }
+                    } else if !self.playback && !same_channels(&self.subscriptions, &subscriptions)
+                    {
+                        self.backend_comms.set_subscriptions(&self.subscriptions);
+                        self.setting_subscriptions = true;
+                    }
+                }
+                WsMessageData::Devices(devices) => {
+                    re_log::debug!("Setting devices...");
+                    if self.selected_device.id.is_empty() && !self.last_selected_device.is_empty() {
+                        if devices.contains(&self.last_selected_device) {
+                            self.set_device(self.last_selected_device.clone());
+                        } else {
+                            // The previously selected device is gone, fall back to no device.
+                            self.last_selected_device = DeviceId::default();
+                        }
+                    }
+                    self.devices_available = Some(devices);
+                }
+                WsMessageData::Pipeline(mut config) => {
+                    config.depth_enabled = config.depth.is_some();
+                    self.device_config.running = Some(config.clone());
+                    let mut subs = self.requested_subscriptions();
+                    if let Some(depth) = config.depth {
+                        subs.push(ChannelId::DepthImage);
+                        if depth.pointcloud.enabled {
+                            subs.push(ChannelId::PointCloud);
+                        }
+                        if depth.stream_disparity {
+                            subs.push(ChannelId::Disparity);
+                        }
+                        if depth.stream_confidence {
+                            subs.push(ChannelId::DepthConfidence);
+                        }
+                    }
+                    // What we asked for, less what the device can't do, keeps what it left out
+                    // for when a device that can is selected.
+                    if config != self.pipeline_config(&self.device_config.config) {
+                        self.device_config.config = config;
+                    }
+                    self.set_subscriptions(&subs);
+                    // Part of starting the pipeline, which gets its own notification.
+                    self.notify_subscriptions_set = false;
+                    if self.device_config.update_in_progress {
+                        re_log::info!("Pipeline started");
+                        self.device_config.history.mark_started();
+                    }
+                    self.device_config.update_in_progress = false;
+                    self.pipeline_request_instant = None;
+                    self.pipeline_attempts = 0;
+                    self.last_error = None;
+                }
+                WsMessageData::ValidateConfig(validation) => {
+                    // Dropped when the config changed since it was sent.
+                    if self.device_config.validation_in_progress
+                        && validation.request_id == self.device_config.validation_request_id
+                    {
+                        self.device_config.validation = Some(validation);
+                        self.device_config.validation_in_progress = false;
+                    }
+                }
+                WsMessageData::Device(device) => {
+                    re_log::debug!("Setting device");
+                    self.device_config.staged = None;
+                    self.device_config.validation = None;
+                    self.device_config.validation_in_progress = false;
+                    self.last_selected_device = device.id.clone();
+                    self.selected_device = device;
+                    self.device_stats = None;
+                    self.temperature_history.clear();
+                    self.frame_throttle.reset();
+                    self.exposure_readback = None;
+                    self.frame_sync.reset();
+                    self.device_config.running = None;
+                    self.backend_comms.received_pipeline_json = None;
+                    // A newly selected device starts out with its own calibration.
+                    self.calibration_source = CalibrationSource::Device;
+                    self.restore_entity_visibility = is_device_id(&self.selected_device.id);
+                    if is_device_id(&self.selected_device.id) {
+                        if let Some((model, confidence_threshold)) =
+                            self.device_ai_models.get(&self.selected_device.id)
+                        {
+                            let config = &mut self.device_config.config;
+                            config.ai_model = model.clone();
+                            config.ai_config.confidence_threshold = *confidence_threshold;
+                        }
+                    }
+                    // No device means the previous one was released, so there's no pipeline to start.
+                    if !self.playback
+                        && should_start_pipeline(
+                            &self.selected_device.id,
+                            &self.pipeline_config(&self.device_config.config),
+                        )
+                    {
+                        self.backend_comms.set_subscriptions(&self.subscriptions);
+                        self.setting_subscriptions = true;
+                        self.pipeline_attempts = 0;
+                        self.request_pipeline();
+                    }
+                }
+                WsMessageData::Log(mut entry) => {
+                    entry.level = entry.level.to_uppercase();
+                    if entry.level == "WARNING" {
+                        entry.level = String::from("WARN");
+                    }
+                    match entry.level.as_str() {
+                        "CRITICAL" | "ERROR" => re_log::error!("Backend: {}", entry.message),
+                        "WARN" => re_log::warn!("Backend: {}", entry.message),
+                        "INFO" => re_log::info!("Backend: {}", entry.message),
+                        "DEBUG" => re_log::debug!("Backend: {}", entry.message),
+                        _ => re_log::trace!("Backend: {}", entry.message),
+                    }
+                    if self.backend_logs.len() == MAX_BACKEND_LOGS {
+                        self.backend_logs.pop_front();
+                    }
+                    self.backend_logs.push_back(entry);
+                }
+                WsMessageData::Stats(stats) => {
+                    self.temperature_history.add(stats.temperature_c);
+                    self.device_stats = Some(stats);
+                }
+                WsMessageData::ExposureReadback(readback) => {
+                    self.exposure_readback = Some(readback);
+                }
+                WsMessageData::Hello(hello) => {
+                    re_log::info!(
+                        "Viewer {} (api {API_VERSION}), backend {} (api {})",
+                        env!("CARGO_PKG_VERSION"),
+                        hello.backend_version,
+                        hello.api_version
+                    );
+                    if !hello.is_compatible() {
+                        re_log::error!(
+                            "The backend speaks api version {}, but the viewer needs {API_VERSION}",
+                            hello.api_version
+                        );
+                    }
+                    self.backend_hello = Some(hello);
+                }
+                WsMessageData::Calibration(_) => {
+                    re_log::info!("Calibration applied");
+                }
+                WsMessageData::CaptureStill(_) => {
+                    re_log::debug!("Still captured");
+                    self.capture_in_progress = false;
+                }
+                WsMessageData::Error(error) => {
+                    self.capture_in_progress = false;
+                    let transient = match error.kind {
+                        ErrorKind::Network => true,
+                        // Retrying with the same config or calibration fails the same way.
+                        ErrorKind::PipelineInvalid | ErrorKind::CalibrationMissing => false,
+                        ErrorKind::DeviceBusy | ErrorKind::Unknown => error.transient,
+                    };
+                    let retrying =
+                        error.action == ErrorAction::None && self.retry_pipeline(transient);
+                    if !retrying {
+                        if let Some(explanation) = error.kind.explanation() {
+                            re_log::error!("{explanation}");
+                        } else {
+                            re_log::error!("Error: {:?}", error.message);
+                        }
+                        self.device_config.update_in_progress = false;
+                        self.pipeline_request_instant = None;
+                        match error.action {
+                            ErrorAction::None => (),
+                            ErrorAction::FullReset => {
+                                self.set_device("".into());
+                            }
+                        }
+                        self.last_error = Some(error);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if self.device_config.update_in_progress
+            && self.pipeline_request_instant.map_or(false, |instant| {
+                instant.elapsed().as_secs() >= PIPELINE_TIMEOUT_SECS
+            })
+            && !self.retry_pipeline(true)
+        {
+            re_log::error!("Timed out waiting for the pipeline to start");
+            self.device_config.update_in_progress = false;
+            self.pipeline_request_instant = None;
+            self.last_error = Some(Error {
+                action: ErrorAction::None,
+                message: String::from("Timed out waiting for the pipeline to start"),
+                transient: true,
+                kind: ErrorKind::Unknown,
+            });
+        }
+
+        if self.pipeline_retry_instant.map_or(false, |instant| {
+            instant.elapsed().as_secs() >= PIPELINE_RETRY_DELAY_SECS
+        }) {
+            self.request_pipeline();
+        }
+
+        // Don't keep waking the backend while the user is doing something else.
+        let regained_focus = window_focused && !self.window_focused;
+        self.window_focused = window_focused;
+        if !window_focused {
+            return;
+        }
+
+        let poll_due = self.poll_instant.map_or(true, |poll_instant| {
+            poll_instant.elapsed().as_secs_f32() >= poll_interval_secs
+        });
+        if poll_due || regained_focus {
+            if self.selected_device.id.is_empty() {
+                self.backend_comms.get_devices();
+            }
+            self.poll_instant = Some(Instant::now());
+        }
+    }
+
+    pub fn set_device(&mut self, device_id: DeviceId) {
+        if self.selected_device.id == device_id {
+            return;
+        }
+        re_log::debug!("Setting device: {:?}", device_id);
+        self.backend_comms.set_device(device_id);
+    }
+
+    /// Select the nth of the devices the backend last reported, for keyboard shortcuts.
+    pub fn select_device_by_index(&mut self, index: usize) {
+        let Some(device_id) = self
+            .devices_available
+            .as_ref()
+            .and_then(|devices| devices.get(index))
+            .cloned() else {
+            return;
+        };
+        self.set_device(device_id);
+    }
+
+    /// Release the selected device so that other processes can open it.
+    pub fn disconnect_device(&mut self) {
+        if self.selected_device.id.is_empty() {
+            return;
+        }
+        re_log::debug!("Disconnecting device: {:?}", self.selected_device.id);
+        self.backend_comms.set_subscriptions(&Vec::new());
+        // Not to be corrected when the backend confirms, ours are restored with the next device.
+        self.setting_subscriptions = true;
+        self.backend_comms.release_device();
+        self.selected_device = Device::default();
+        // Don't reconnect to it on the next reload.
+        self.last_selected_device = DeviceId::default();
+        self.device_stats = None;
+        self.temperature_history.clear();
+        self.frame_throttle.reset();
+        self.exposure_readback = None;
+        self.frame_sync.reset();
+        self.device_config.running = None;
+        self.backend_comms.sent_pipeline_json = None;
+        self.backend_comms.received_pipeline_json = None;
+        self.device_config.update_in_progress = false;
+        self.pipeline_request_instant = None;
+        self.pipeline_retry_instant = None;
+    }
+
+    /// Make the backend close every handle to the selected device, including ones left behind
+    /// by earlier sessions, then try to start the pipeline again.
+    pub fn force_release_and_retry(&mut self) {
+        if !is_device_id(&self.selected_device.id) {
+            return;
+        }
+        re_log::info!("Force releasing device {:?}", self.selected_device.id);
+        self.last_error = None;
+        self.backend_comms
+            .force_release_device(self.selected_device.id.clone());
+        self.pipeline_attempts = 0;
+        self.request_pipeline();
+    }
+
+    /// Replace the current config with a saved preset and restart the pipeline with it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_preset(&mut self, name: &str) {
+        let Some(mut config) = DeviceConfigState::read_preset(name) else {
+            return;
+        };
+        if !self.selected_device.supports(&config) {
+            re_log::error!(
+                "Preset {name:?} uses a color camera resolution of {} or depth, which the current device doesn't support",
+                config.color_camera.resolution
+            );
+            return;
+        }
+        self.device_config.selected_preset = name.to_owned();
+        self.set_device_config(&mut config);
+    }
+
+    /// Apply a config shared as json, e.g. in a bug report, the format is [`DeviceConfig`]'s serde impl.
+    pub fn paste_device_config(&mut self, json: &str) {
+        let mut config = match serde_json::from_str::<DeviceConfig>(json.trim()) {
+            Ok(config) => config,
+            Err(err) => {
+                re_log::error!("The pasted text isn't a valid device config: {err}");
+                return;
+            }
+        };
+        if !self.selected_device.supports(&config) {
+            re_log::error!(
+                "The pasted config uses a color camera resolution of {} or depth, which the current device doesn't support",
+                config.color_camera.resolution
+            );
+            return;
+        }
+        config.depth_enabled = config.depth.is_some();
+        self.set_device_config(&mut config);
+    }
+
+    /// Restart the pipeline of the selected device with the default config.
+    pub fn reset_device_config(&mut self) {
+        self.device_config.selected_preset.clear();
+        self.set_device_config(&mut DeviceConfig::default());
+    }
+
+    /// Go back to the previously applied config.
+    pub fn undo_device_config(&mut self) {
+        if let Some(mut config) = self.device_config.history.undo_config().cloned() {
+            self.device_config.selected_preset.clear();
+            self.set_device_config(&mut config);
+        }
+    }
+
+    /// Re-apply a config that was undone.
+    pub fn redo_device_config(&mut self) {
+        if let Some(mut config) = self.device_config.history.redo_config().cloned() {
+            self.device_config.selected_preset.clear();
+            self.set_device_config(&mut config);
+        }
+    }
+
+    pub fn capture_still(&mut self) {
+        if self.capture_in_progress || self.selected_device.id.is_empty() {
+            return;
+        }
+        self.backend_comms
+            .capture_still(self.selected_device.id.clone());
+        self.capture_in_progress = true;
+    }
+
+    pub fn set_device_config(&mut self, config: &mut DeviceConfig) {
+        if self.playback
+            || !self
+                .backend_comms
+                .ws
+                .connected
+                .load(std::sync::atomic::Ordering::SeqCst)
+            || self.selected_device.id == ""
+        {
+            return;
+        }
+        // Sending the same config would only restart the pipeline, unless the last attempt failed.
+        if self.last_error.is_none()
+            && (self.device_config.is_applied(&self.pipeline_config(config))
+                || self.device_config.is_pending(config))
+        {
+            re_log::debug!("Device config unchanged, not restarting the pipeline");
+            return;
+        }
+        config.normalize();
+        // Only a config that is actually sent replaces the current one.
+        if let Err(err) = config.check() {
+            re_log::error!("{err}");
+            return;
+        }
+        self.device_config.config = config.clone();
+        self.device_ai_models.insert(
+            self.selected_device.id.clone(),
+            (
+                config.ai_model.clone(),
+                config.ai_config.confidence_threshold,
+            ),
+        );
+        re_log::info!("Creating pipeline...");
+        self.pipeline_attempts = 0;
+        self.request_pipeline();
+    }
+
+    /// Ask the backend whether the staged config would start, without restarting the running pipeline.
+    pub fn validate_staged_config(&mut self) {
+        let Some(mut config) = self.device_config.staged.take() else {
+            return;
+        };
+        config.normalize();
+        let checked = config.check();
+        let request = PipelineRequest::from(&self.pipeline_config(&config));
+        self.device_config.staged = Some(config);
+        if let Err(err) = checked {
+            // No need to bother the backend with what it'd reject anyway.
+            self.device_config.validation = Some(ConfigValidation {
+                errors: vec![err],
+                ..Default::default()
+            });
+            return;
+        }
+        self.device_config.validation_request_id += 1;
+        self.backend_comms
+            .validate_config(self.device_config.validation_request_id, &request);
+        self.device_config.validation = None;
+        self.device_config.validation_in_progress = true;
+    }
+
+    /// Only stream `region` of the color frame, given relative to the frame as it's streamed now.
+    pub fn crop_color_camera(&mut self, region: (f32, f32, f32, f32)) {
+        let crop_roi = self.device_config.config.color_camera.crop_within(region);
+        // Crop on top of the changes that are still held back, if any.
+        let mut config = self
+            .device_config
+            .staged
+            .clone()
+            .unwrap_or_else(|| self.device_config.config.clone());
+        config.color_camera.crop_roi = Some(crop_roi);
+        self.change_device_config(config);
+    }
+
+    /// Applies a config the user changed, or holds it back if they want to validate it first.
+    pub fn change_device_config(&mut self, mut config: DeviceConfig) {
+        if self.device_config.validate_before_apply {
+            self.device_config.stage(config);
+        } else {
+            self.set_device_config(&mut config);
+        }
+    }
+
+    /// Restart the pipeline with the staged config.
+    pub fn apply_staged_config(&mut self) {
+        if let Some(mut config) = self.device_config.staged.take() {
+            self.device_config.validation = None;
+            self.device_config.validation_in_progress = false;
+            self.set_device_config(&mut config);
+        }
+    }
+
+    /// `config` as it's run on the selected device, without what the device can't do.
+    ///
+    /// The config itself is left as is, so nothing is lost when a more capable device is selected.
+    fn pipeline_config(&self, config: &DeviceConfig) -> DeviceConfig {
+        let mut config = config.clone();
+        if is_device_id(&self.selected_device.id) {
+            config.limit_to(&self.selected_device);
+        }
+        config
+    }
+
+    fn request_pipeline(&mut self) {
+        if self.playback {
+            return;
+        }
+        self.device_config.config.resolve_mono_sockets();
+        let request = PipelineRequest::from(&self.pipeline_config(&self.device_config.config));
+        if let Err(err) = request.validate() {
+            re_log::error!("{err}");
+            return;
+        }
+        self.device_config.history.push(&self.device_config.config);
+        self.backend_comms.set_pipeline(&request);
+        self.device_config.update_in_progress = true;
+        self.pipeline_request_instant = Some(Instant::now());
+        self.pipeline_retry_instant = None;
+        self.pipeline_attempts += 1;
+    }
+
+    /// Schedule the pending pipeline request to be sent again after a failure.
+    ///
+    /// Returns false if the failure should be surfaced instead, because it isn't transient,
+    /// no pipeline was pending or we ran out of attempts.
+    fn retry_pipeline(&mut self, transient: bool) -> bool {
+        if !transient
+            || self.pipeline_request_instant.is_none()
+            || self.pipeline_attempts >= MAX_PIPELINE_ATTEMPTS
+        {
+            self.pipeline_attempts = 0;
+            return false;
+        }
+        re_log::warn!(
+            "Failed to start the pipeline, retrying ({}/{MAX_PIPELINE_ATTEMPTS})",
+            self.pipeline_attempts + 1
+        );
+        self.pipeline_request_instant = None;
+        self.pipeline_retry_instant = Some(Instant::now());
+        true
+    }
+}
+
+pub type DeviceId = String; // i64 because of serialization
+
+/// The backend reports `-1` when it has no device, just like we use an empty id.
+fn is_device_id(id: &DeviceId) -> bool {
+    !id.is_empty() && id != "-1"
+}
+
+/// Whether selecting the device should start a pipeline, rather than just recording the device.
+fn should_start_pipeline(device_id: &DeviceId, config: &DeviceConfig) -> bool {
+    is_device_id(device_id) && config.has_streams()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ws::WebSocket;
+    use super::*;
+
+    /// A state with a device selected, whose messages to the backend can be read back.
+    fn connected_state() -> (
+        State,
+        crossbeam_channel::Sender<ewebsock::WsMessage>,
+        crossbeam_channel::Receiver<ewebsock::WsMessage>,
+    ) {
+        let mut state = State::default();
+        let (ws, backend, sent) = WebSocket::loopback();
+        state.backend_comms.ws = ws;
+        state.selected_device.id = "device".to_owned();
+        (state, backend, sent)
+    }
+
+    /// Have the state handle `message` as if the backend had sent it.
+    fn receive(
+        state: &mut State,
+        backend: &crossbeam_channel::Sender<ewebsock::WsMessage>,
+        message: serde_json::Value,
+    ) {
+        backend
+            .send(ewebsock::WsMessage::Text(message.to_string()))
+            .unwrap();
+        state.update(1.0, true);
+    }
+
+    /// Kinds of the messages sent to the backend since the last call.
+    fn sent_kinds(sent: &crossbeam_channel::Receiver<ewebsock::WsMessage>) -> Vec<String> {
+        sent.try_iter()
+            .filter_map(|message| match message {
+                ewebsock::WsMessage::Text(text) => {
+                    let message: serde_json::Value = serde_json::from_str(&text).ok()?;
+                    Some(message.get("type")?.as_str()?.to_owned())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    const CAMERA_CHANNELS: [ChannelId; 3] = [
+        ChannelId::ColorImage,
+        ChannelId::LeftMono,
+        ChannelId::RightMono,
+    ];
+
+    #[test]
+    fn test_isp_scale_validation() {
+        for (isp_scale, valid) in [
+            ((1, 1), true),
+            ((2, 3), true),
+            ((1, 2), true),
+            ((3, 2), false),
+            ((0, 1), false),
+            ((1, 0), false),
+        ] {
+            let config = ColorCameraConfig {
+                isp_scale,
+                ..Default::default()
+            };
+            assert_eq!(config.isp_scale_is_valid(), valid, "{isp_scale:?}");
+        }
+    }
+
+    #[test]
+    fn test_unsubscribed_cameras_are_removed() {
+        for channel in CAMERA_CHANNELS {
+            let subscriptions = all_subscriptions()
+                .into_iter()
+                .filter(|c| *c != channel)
+                .collect_vec();
+            let remove_channels = channels_to_remove(&DeviceConfig::default(), &subscriptions);
+            assert!(
+                remove_channels.contains(&channel),
+                "{channel:?} should be removed when unsubscribed"
+            );
+            for other in CAMERA_CHANNELS.iter().filter(|c| **c != channel) {
+                assert!(!remove_channels.contains(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_entity_paths_map_to_channels() {
+        for (path, channel) in [
+            ("world/camera/image/rgb", ChannelId::ColorImage),
+            ("world/camera/left", ChannelId::LeftMono),
+            ("world/camera/right", ChannelId::RightMono),
+            ("world/camera/depth", ChannelId::DepthImage),
+            ("world/camera/image/depth", ChannelId::DepthImage),
+            ("world/camera/disparity", ChannelId::Disparity),
+            ("world/camera/confidence", ChannelId::DepthConfidence),
+            ("world/point_cloud", ChannelId::PointCloud),
+            ("world/camera/image/detections", ChannelId::Detections),
+            (
+                "world/camera/spatial_detections",
+                ChannelId::SpatialDetections,
+            ),
+            ("preview/color", ChannelId::ColorPreview),
+            ("world/camera/left_rectified", ChannelId::LeftRectified),
+            ("world/camera/right_rectified", ChannelId::RightRectified),
+            ("imu_data", ChannelId::ImuData),
+        ] {
+            assert_eq!(
+                DEPTHAI_ENTITY_HASHES.get(&EntityPath::from(path).hash()),
+                Some(&channel),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_fps_per_resolution() {
+        assert_eq!(ColorCameraResolution::THE_1080_P.max_fps(), 60);
+        assert_eq!(ColorCameraResolution::THE_4_K.max_fps(), 30);
+        assert_eq!(MonoCameraResolution::THE_400_P.max_fps(), 120);
+    }
+
+    #[test]
+    fn test_fit_to_bandwidth_budget() {
+        let mut config = DeviceConfig::default();
+        let mut depth = DepthConfig::default();
+        depth.pointcloud.enabled = true;
+        config.depth = Some(depth);
+        let bandwidth = HashMap::from([
+            (ChannelId::ColorImage, 40_000_000),
+            (ChannelId::PointCloud, 20_000_000),
+            (ChannelId::DepthImage, 5_000_000),
+        ]);
+
+        assert!(fit_to_bandwidth_budget(&config, &bandwidth, 100_000_000).is_none());
+
+        // Dropping the point cloud and a quarter of the color pixels is enough.
+        let suggestion = fit_to_bandwidth_budget(&config, &bandwidth, 20_000_000).unwrap();
+        assert!(!suggestion.config.depth.unwrap().pointcloud.enabled);
+        assert_eq!(suggestion.config.color_camera.isp_scale, (1, 2));
+        assert_eq!(suggestion.config.color_camera.fps, config.color_camera.fps);
+        assert_eq!(suggestion.actions.len(), 2);
+        assert!(suggestion.bytes_per_sec <= 20_000_000);
+
+        // An impossible budget bottoms out instead of looping forever.
+        let suggestion = fit_to_bandwidth_budget(&config, &bandwidth, 1).unwrap();
+        assert_eq!(suggestion.config.color_camera.isp_scale, (1, 4));
+        assert_eq!(suggestion.config.color_camera.fps, MIN_BUDGET_FPS);
+        assert_eq!(suggestion.config.right_camera.fps, MIN_BUDGET_FPS);
+
+        // Scales with a large numerator, e.g. from a preset, don't overflow.
+        config.color_camera.isp_scale = (64, 64);
+        let suggestion = fit_to_bandwidth_budget(&config, &bandwidth, 1).unwrap();
+        assert_eq!(suggestion.config.color_camera.isp_scale, (64, 64));
+        assert_eq!(suggestion.config.color_camera.fps, MIN_BUDGET_FPS);
+    }
+
+    #[test]
+    fn test_config_limited_to_device() {
+        let mut config = DeviceConfig {
+            depth: Some(DepthConfig::default()),
+            depth_enabled: true,
+            ..Default::default()
+        };
+        config.color_camera.resolution = ColorCameraResolution::THE_4_K;
+
+        let oak_d = Device {
+            model: "OAK-D".to_owned(),
+            ..Default::default()
+        };
+        assert!(oak_d.supports(&config));
+        let mut limited = config.clone();
+        limited.limit_to(&oak_d);
+        assert_eq!(limited, config);
+
+        let oak_1 = Device {
+            model: "OAK-1".to_owned(),
+            supported_color_resolutions: vec![ColorCameraResolution::THE_1080_P],
+            ..Default::default()
+        };
+        assert!(!oak_1.supports(&config));
+        config.limit_to(&oak_1);
+        assert!(oak_1.supports(&config));
+        assert_eq!(
+            config.color_camera.resolution,
+            ColorCameraResolution::THE_1080_P
+        );
+        assert!(config.depth.is_none() && !config.left_camera.enabled);
+    }
+
+    #[test]
+    fn test_selecting_a_limited_device_keeps_the_config() {
+        let (mut state, backend, _) = connected_state();
+        state.device_config.config.depth = Some(DepthConfig::default());
+        state.device_config.config.depth_enabled = true;
+        let config = state.device_config.config.clone();
+
+        let oak_1 =
+            serde_json::json!({"type": "Device", "data": {"id": "device", "model": "OAK-1"}});
+        receive(&mut state, &backend, oak_1);
+        assert_eq!(state.device_config.config, config);
+
+        // Only what's sent leaves out what the device can't do.
+        let sent: serde_json::Value =
+            serde_json::from_str(state.backend_comms.sent_pipeline_json.as_ref().unwrap()).unwrap();
+        let request = &sent["data"]["Pipeline"];
+        assert!(request["depth"].is_null());
+        assert_eq!(request["left_camera"]["enabled"], false);
+    }
+
+    #[test]
+    fn test_pipeline_request_wire_format() {
+        let config = DeviceConfig {
+            depth: Some(DepthConfig::default()),
+            depth_enabled: true,
+            ..Default::default()
+        };
+        let request = PipelineRequest::from(&config);
+        assert_eq!(request.validate(), Ok(()));
+
+        // The request is the config as it has always been sent, plus the version.
+        let mut expected = serde_json::to_value(&config).unwrap();
+        expected["version"] = API_VERSION.into();
+        assert_eq!(serde_json::to_value(&request).unwrap(), expected);
+
+        let mut request = request;
+        request.color_camera.fps = 0;
+        request.right_camera.board_socket = BoardSocket::LEFT;
+        assert_eq!(
+            request.validate().unwrap_err().fields,
+            vec!["color_camera.fps", "right_camera.board_socket"]
+        );
+
+        // Sockets left on AUTO resolve to the stereo pair, unless the other camera took that socket.
+        request.color_camera.fps = 30;
+        request.left_camera.board_socket = BoardSocket::AUTO;
+        request.right_camera.board_socket = BoardSocket::AUTO;
+        assert_eq!(request.validate(), Ok(()));
+        request.left_camera.board_socket = BoardSocket::RIGHT;
+        assert_eq!(
+            request.validate().unwrap_err().fields,
+            vec!["right_camera.board_socket"]
+        );
+
+        // A field that fails several checks is reported once.
+        request.left_camera.board_socket = BoardSocket::LEFT;
+        request.right_camera.fps = 0;
+        request.right_camera.enabled = false;
+        assert_eq!(
+            request.validate().unwrap_err().fields,
+            vec!["right_camera.enabled", "right_camera.fps"]
+        );
+    }
+
+    #[test]
+    fn test_error_kind_deserialization() {
+        let error: Error = serde_json::from_str(
+            r#"{"action": "None", "message": "no calibration", "kind": "CalibrationMissing"}"#,
+        )
+        .unwrap();
+        assert_eq!(error.kind, ErrorKind::CalibrationMissing);
+
+        // Older backends send the kind as `code`, with the old name for a busy device.
+        let error: Error =
+            serde_json::from_str(r#"{"action": "None", "message": "busy", "code": "DeviceInUse"}"#)
+                .unwrap();
+        assert_eq!(error.kind, ErrorKind::DeviceBusy);
+        assert!(error.kind.explanation().is_some());
+
+        // Backends that don't send a kind, or send one we don't know, still parse.
+        for json in [
+            r#"{"action": "None", "message": "oops"}"#,
+            r#"{"action": "None", "message": "oops", "kind": "SomethingNew"}"#,
+        ] {
+            let error: Error = serde_json::from_str(json).unwrap();
+            assert_eq!(error.kind, ErrorKind::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_unknown_ws_message_type() {
+        let message: WsMessage =
+            serde_json::from_str(r#"{"type": "SomethingNew", "data": {"value": 1}}"#).unwrap();
+        assert!(matches!(message.kind, WsMessageType::Unknown(kind) if kind == "SomethingNew"));
+
+        let message: WsMessage =
+            serde_json::from_str(r#"{"type": "Devices", "data": ["1844301011B546F500"]}"#).unwrap();
+        assert!(matches!(message.data, WsMessageData::Devices(devices) if devices.len() == 1));
+    }
+
+    #[test]
+    fn test_reduced_load() {
+        let mut config = DeviceConfig {
+            depth: Some(DepthConfig::default()),
+            depth_enabled: true,
+            ..Default::default()
+        };
+        config.color_camera.resolution = ColorCameraResolution::THE_4_K;
+        config.left_camera.fps = 1;
+        config.depth.as_mut().unwrap().filters.temporal = true;
+
+        let reduced = config.reduced_load();
+        assert_eq!(reduced.color_camera.fps, config.color_camera.fps / 2);
+        assert_eq!(
+            reduced.color_camera.resolution,
+            ColorCameraResolution::THE_1080_P
+        );
+        assert_eq!(reduced.left_camera.fps, MIN_FPS);
+        assert!(!reduced.depth.unwrap().filters.temporal);
+    }
+
+    #[test]
+    fn test_depth_summary() {
+        let mut depth = DepthConfig::default();
+        assert_eq!(
+            depth.summary(),
+            "High Density preset, median 7x7, confidence 230, aligned to right mono"
+        );
+        depth.decimation_factor = 2;
+        depth.disparity_shift = 30;
+        depth.filters.spatial = true;
+        depth.filters.speckle = true;
+        depth.align = BoardSocket::RGB;
+        assert_eq!(
+            depth.summary(),
+            "High Density preset, median 7x7, confidence 230, decimated 2x, \
+            disparity shift 30, spatial, speckle filtering, aligned to color"
+        );
+    }
+
+    #[test]
+    fn test_imu_scalar_rows() {
+        use re_log_types::{component_types::Point3D, DataRow, RowId, TimePoint};
+
+        let imu_data = ImuData {
+            accel: Point3D::new(0.0, 9.81, 0.0),
+            gyro: Point3D::new(0.1, 0.2, 0.3),
+            mag: None,
+            orientation: Default::default(),
+        };
+        let row = DataRow::from_cells1(
+            RowId::random(),
+            ImuData::entity_path(),
+            TimePoint::default(),
+            1,
+            vec![imu_data.clone()],
+        );
+        let rows = imu_scalar_rows(&row);
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.entity_path().to_string())
+                .collect_vec(),
+            [
+                "imu_data/accel/x",
+                "imu_data/accel/y",
+                "imu_data/accel/z",
+                "imu_data/gyro/x",
+                "imu_data/gyro/y",
+                "imu_data/gyro/z",
+            ]
+        );
+
+        let other = DataRow::from_cells1(
+            RowId::random(),
+            "world/camera/depth",
+            TimePoint::default(),
+            1,
+            vec![imu_data],
+        );
+        assert!(imu_scalar_rows(&other).is_empty());
+    }
+
+    #[test]
+    fn test_crop_within() {
+        let mut config = ColorCameraConfig::default();
+        assert!(config.crop_roi_is_valid());
+        assert_eq!(
+            config.crop_within((0.25, 0.5, 0.75, 1.0)),
+            (0.25, 0.5, 0.75, 1.0)
+        );
+
+        // Cropping a cropped frame narrows it down further.
+        config.crop_roi = Some((0.5, 0.0, 1.0, 0.5));
+        assert_eq!(
+            config.crop_within((0.0, 0.5, 0.5, 1.0)),
+            (0.5, 0.25, 0.75, 0.5)
+        );
+
+        config.crop_roi = Some((0.5, 0.0, 0.25, 1.0));
+        assert!(!config.crop_roi_is_valid());
+    }
+
+    #[test]
+    fn test_going_live_restarts_streaming() {
+        let (mut state, _, sent) = connected_state();
+        state.update(1.0, true);
+        sent.try_iter().for_each(drop);
+
+        state.set_playback(true);
+        state.set_playback(false);
+        let kinds = sent_kinds(&sent);
+        assert!(kinds.contains(&"Subscriptions".to_owned()));
+        assert!(kinds.contains(&"Pipeline".to_owned()));
+    }
+
+    #[test]
+    fn test_crop_is_staged_when_validating_first() {
+        let (mut state, _, sent) = connected_state();
+        state.update(1.0, true);
+        sent.try_iter().for_each(drop);
+        state.device_config.validate_before_apply = true;
+        let mut staged = state.device_config.config.clone();
+        staged.color_camera.fps = 15;
+        state.device_config.stage(staged);
+
+        state.crop_color_camera((0.0, 0.0, 0.5, 0.5));
+        assert!(sent_kinds(&sent).is_empty());
+        let staged = state.device_config.staged.as_ref().unwrap();
+        assert_eq!(staged.color_camera.crop_roi, Some((0.0, 0.0, 0.5, 0.5)));
+        assert_eq!(staged.color_camera.fps, 15);
+        assert_eq!(state.device_config.config.color_camera.crop_roi, None);
+    }
+
+    #[test]
+    fn test_frame_throttle() {
+        const MS: i64 = 1_000_000;
+        let mut throttle = FrameThrottle::default();
+        // 30 FPS with some jitter, capped to 10.
+        let accepted = (0..30)
+            .filter(|frame| {
+                let jitter = if frame % 2 == 0 { MS } else { -MS };
+                throttle.accept(ChannelId::ColorImage, frame * 33 * MS + jitter, 10)
+            })
+            .count();
+        assert_eq!(accepted, 10);
+
+        // Each stream is capped on its own.
+        assert!(throttle.accept(ChannelId::DepthImage, 0, 10));
+        // After a stall the next frame goes through right away.
+        assert!(throttle.accept(ChannelId::ColorImage, 5_000 * MS, 10));
+        assert!(!throttle.accept(ChannelId::ColorImage, 5_033 * MS, 10));
+    }
+
+    #[test]
+    fn test_frame_sync_warning_scales_with_fps() {
+        assert!((FrameSync::warning_secs(10) - 0.12).abs() < 1e-6);
+        assert!(FrameSync::warning_secs(60) < FrameSync::warning_secs(30));
+        assert!(FrameSync::warning_secs(0).is_finite());
+
+        let mut config = DeviceConfig::default();
+        config.left_camera.fps = 15;
+        assert_eq!(config.slowest_camera_fps(), Some(15));
+        config.left_camera.enabled = false;
+        assert_eq!(config.slowest_camera_fps(), Some(config.color_camera.fps));
+    }
+
+    #[test]
+    fn test_frame_sync() {
+        let subscriptions = [ChannelId::ColorImage, ChannelId::DepthImage];
+        let start = Instant::now();
+        let mut frame_sync = FrameSync::default();
+        frame_sync.on_frame(ChannelId::ColorImage, 1_000_000_000, &subscriptions, start);
+        assert_eq!(frame_sync.max_skew_secs(start), None);
+
+        frame_sync.on_frame(ChannelId::DepthImage, 1_020_000_000, &subscriptions, start);
+        frame_sync.on_frame(ChannelId::ColorImage, 1_030_000_000, &subscriptions, start);
+        // Unsubscribed streams and the IMU don't count.
+        frame_sync.on_frame(ChannelId::LeftMono, 2_000_000_000, &subscriptions, start);
+        frame_sync.on_frame(ChannelId::ImuData, 2_000_000_000, &subscriptions, start);
+        let skew = frame_sync.max_skew_secs(start).unwrap();
+        assert!((skew - 0.02).abs() < 1e-6);
+
+        let later = start + std::time::Duration::from_secs_f32(FrameSync::WINDOW_SECS + 1.0);
+        assert_eq!(frame_sync.max_skew_secs(later), None);
+    }
+
+    #[test]
+    fn test_config_changes() {
+        let previous = DeviceConfig::default();
+        assert!(previous.changes_from(&previous).is_empty());
+
+        let mut config = previous.clone();
+        config.color_camera.fps = 15;
+        config.depth = Some(DepthConfig::default());
+        let changes = config.changes_from(&previous);
+        assert!(changes.contains(&"color_camera.fps: 30 → 15".to_owned()));
+        assert!(changes
+            .iter()
+            .any(|change| change.starts_with("depth: null → {")));
+    }
+
+    #[test]
+    fn test_config_history() {
+        let config = |fps| {
+            let mut config = DeviceConfig::default();
+            config.color_camera.fps = fps;
+            config
+        };
+        let mut history = ConfigHistory::default();
+        assert!(!history.can_undo() && !history.can_redo());
+
+        history.push(&config(30));
+        history.mark_started();
+        history.push(&config(20));
+        history.push(&config(20)); // A retry doesn't add an entry.
+        assert_eq!(history.iter().count(), 2);
+
+        assert_eq!(history.undo_config(), Some(&config(30)));
+        // Nothing moves until it's applied, e.g. it may turn out to be invalid.
+        assert!(!history.can_redo());
+        // Applying what undo returned doesn't add an entry either.
+        history.push(&config(30));
+        assert!(history.can_redo());
+        assert_eq!(history.redo_config(), Some(&config(20)));
+        history.push(&config(20));
+        assert_eq!(history.redo_config(), None);
+
+        // A new config after an undo drops what was undone.
+        history.push(&config(30));
+        history.push(&config(10));
+        assert!(!history.can_redo());
+        let started = history.iter().map(|(entry, _)| entry.started).collect_vec();
+        assert_eq!(started, vec![true, false]);
+
+        for fps in 0..MAX_CONFIG_HISTORY as u8 * 2 {
+            history.push(&config(fps));
+        }
+        assert_eq!(history.iter().count(), MAX_CONFIG_HISTORY);
+    }
+
+    #[test]
+    fn test_validate_calibration() {
+        let calibration = serde_json::json!({
+            "boardName": "OAK-D",
+            "cameraData": [
+                [0, { "intrinsicMatrix": [[860.0, 0.0, 640.0], [0.0, 860.0, 360.0], [0.0, 0.0, 1.0]] }],
+            ],
+        });
+        assert_eq!(validate_calibration(&calibration), Ok(()));
+
+        assert_eq!(
+            validate_calibration(&serde_json::json!({ "boardName": "OAK-D" })),
+            Err(CalibrationError::NoCameraData)
+        );
+        let calibration = serde_json::json!({
+            "cameraData": [[0, { "intrinsicMatrix": [[860.0, 0.0], [0.0, 860.0]] }]],
+        });
+        assert_eq!(
+            validate_calibration(&calibration),
+            Err(CalibrationError::InvalidIntrinsics(0))
+        );
+    }
+
+    #[test]
+    fn test_pipeline_needs_a_device() {
+        let config = DeviceConfig::default();
+        assert!(should_start_pipeline(
+            &"18443010C1E4681200".to_owned(),
+            &config
+        ));
+        assert!(!should_start_pipeline(&"-1".to_owned(), &config));
+        assert!(!should_start_pipeline(&DeviceId::default(), &config));
+
+        let empty_config = DeviceConfig {
+            color_camera: ColorCameraConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            left_camera: MonoCameraConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            right_camera: MonoCameraConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!should_start_pipeline(
+            &"18443010C1E4681200".to_owned(),
+            &empty_config
+        ));
+    }
+
+    #[test]
+    fn test_restored_subscriptions_are_reconciled() {
+        let (mut state, backend, sent) = connected_state();
+        // Restored from the previous session, the user had dropped the point cloud.
+        let restored = vec![ChannelId::ColorImage, ChannelId::DepthImage];
+        state.subscriptions = restored.clone();
+        // Connecting asks for them.
+        state.update(1.0, true);
+        assert!(sent_kinds(&sent).contains(&"Subscriptions".to_owned()));
+
+        // The reply leaves out what can't be streamed right now, e.g. depth is disabled,
+        // that is still kept for when it can.
+        let color_only = serde_json::json!({"type": "Subscriptions", "data": ["ColorImage"]});
+        receive(&mut state, &backend, color_only.clone());
+        assert_eq!(state.subscriptions, restored);
+        assert!(!sent_kinds(&sent).contains(&"Subscriptions".to_owned()));
+
+        // Streaming what it was asked for before, e.g. by an earlier session, the backend is
+        // told what we want.
+        let everything = serde_json::json!({"type": "Subscriptions", "data": all_subscriptions()});
+        receive(&mut state, &backend, everything);
+        assert_eq!(state.subscriptions, restored);
+        assert!(sent_kinds(&sent).contains(&"Subscriptions".to_owned()));
+
+        receive(&mut state, &backend, color_only);
+        assert_eq!(state.subscriptions, restored);
+        assert!(!sent_kinds(&sent).contains(&"Subscriptions".to_owned()));
+    }
+
+    #[test]
+    fn test_detection_label() {
+        assert_eq!(
+            detection_label(Some("person".to_owned()), Some(1)),
+            "person"
+        );
+        assert_eq!(detection_label(None, Some(15)), "Class 15");
+        assert_eq!(detection_label(None, None), "Detection");
+    }
+
+    #[test]
+    fn test_picked_color_mappers_are_remembered() {
+        let mut state = State::default();
+        let depth_path = EntityPath::from("world/camera/depth");
+        let mut views = [(); 2].map(|_| {
+            SpaceView::new(
+                crate::ui::ViewCategory::Spatial,
+                &depth_path,
+                &[depth_path.clone()],
+            )
+        });
+        let set_color_mapper = |view: &mut SpaceView, colormap| {
+            let properties_map = view.data_blueprint.data_blueprints_individual();
+            let mut properties = properties_map.get(&depth_path);
+            properties.color_mapper =
+                EditableAutoValue::UserEdited(ColorMapper::Colormap(colormap));
+            properties_map.set(depth_path.clone(), properties);
+        };
+        set_color_mapper(&mut views[0], Colormap::Inferno);
+        set_color_mapper(&mut views[1], Colormap::Magma);
+        let remembered = state
+            .channel_color_mappers
+            .get(&ChannelId::DepthImage)
+            .copied();
+
+        // Two views showing the depth differently don't override each other every frame.
+        state.sync_color_mappers(views.iter_mut());
+        state.sync_color_mappers(views.iter_mut());
+        assert_eq!(
+            state
+                .channel_color_mappers
+                .get(&ChannelId::DepthImage)
+                .copied(),
+            remembered
+        );
+
+        set_color_mapper(&mut views[1], Colormap::Plasma);
+        state.sync_color_mappers(views.iter_mut());
+        state.sync_color_mappers(views.iter_mut());
+        assert_eq!(
+            state.channel_color_mappers.get(&ChannelId::DepthImage),
+            Some(&ColorMapper::Colormap(Colormap::Plasma))
+        );
+    }
+
+    #[test]
+    fn test_space_view_display_name() {
+        let mut state = State::default();
+        state
+            .channel_display_names
+            .insert(ChannelId::ColorImage, "Front".to_owned());
+        let space_path = EntityPath::from("world/camera/image");
+        let space_view = SpaceView::new(
+            crate::ui::ViewCategory::Spatial,
+            &space_path,
+            &[
+                EntityPath::from(COLOR_IMAGE_ENTITY_PATH),
+                EntityPath::from("world/camera/image/detections"),
+            ],
+        );
+        assert_eq!(state.entity_display_name(&space_path), None);
+        assert_eq!(
+            state.space_view_display_name(space_view.data_blueprint.entity_paths()),
+            Some("Front")
+        );
+
+        let namespaced = EntityPath::from("18443010C1E5D40F00/world/camera/image/rgb");
+        assert_eq!(state.space_view_display_name([&namespaced]), Some("Front"));
+
+        // A view of differently named streams keeps its own name.
+        state
+            .channel_display_names
+            .insert(ChannelId::Detections, "People".to_owned());
+        assert_eq!(
+            state.space_view_display_name(space_view.data_blueprint.entity_paths()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_color_image_entity_path() {
+        let mut state = State::default();
+        state.selected_device.id = "18443010C1E5D40F00".to_owned();
+        assert_eq!(
+            state.color_image_entity_path(&EntityPath::from("world/camera/image")),
+            Some(EntityPath::from(COLOR_IMAGE_ENTITY_PATH))
+        );
+        assert_eq!(
+            state.color_image_entity_path(&EntityPath::from(
+                "18443010C1E5D40F00/world/camera/image"
+            )),
+            Some(EntityPath::from(
+                "18443010C1E5D40F00/world/camera/image/rgb"
+            ))
+        );
+        // Only the selected device's config can be cropped.
+        assert_eq!(
+            state.color_image_entity_path(&EntityPath::from(
+                "14442C10D13EABCE00/world/camera/image"
+            )),
+            None
+        );
+        assert_eq!(
+            state.color_image_entity_path(&EntityPath::from("world/camera")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_device_namespaced_entity_paths() {
+        assert_eq!(
+            device_channel_from_entity_path(&EntityPath::from("world/point_cloud")),
+            Some((None, ChannelId::PointCloud))
+        );
+        assert_eq!(
+            device_channel_from_entity_path(&EntityPath::from(
+                "18443010C1E5D40F00/world/point_cloud"
+            )),
+            Some((Some("18443010C1E5D40F00".to_owned()), ChannelId::PointCloud))
+        );
+        assert_eq!(
+            device_channel_from_entity_path(&EntityPath::from("some/other/entity")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_subscriptions_follow_the_selected_devices_views() {
+        let (mut state, _, _) = connected_state();
+        state.selected_device.id = "18443010C1E5D40F00".to_owned();
+        state.device_config.config.depth = Some(DepthConfig::default());
+        state.subscriptions.clear();
+        let other_point_cloud = EntityPath::from("14442C10D13EABCE00/world/point_cloud");
+        let left = EntityPath::from("world/camera/left");
+        let views = [
+            SpaceView::new(
+                crate::ui::ViewCategory::Spatial,
+                &EntityPath::from("14442C10D13EABCE00/world"),
+                &[other_point_cloud],
+            ),
+            SpaceView::new(crate::ui::ViewCategory::Spatial, &left, &[left.clone()]),
+        ];
+        let visible_space_views = views.iter().collect_vec();
+
+        let visible_channels = state.visible_channels_per_device(&visible_space_views);
+        assert_eq!(
+            visible_channels.get("14442C10D13EABCE00"),
+            Some(&vec![ChannelId::PointCloud])
+        );
+        assert_eq!(
+            visible_channels.get("18443010C1E5D40F00"),
+            Some(&vec![ChannelId::LeftMono])
+        );
+
+        // The other device's point cloud doesn't subscribe the selected device to its own.
+        state.set_subscriptions_from_space_views(visible_space_views);
+        assert!(state.subscriptions.contains(&ChannelId::LeftMono));
+        assert!(!state.subscriptions.contains(&ChannelId::PointCloud));
+
+        assert_eq!(
+            state.devices_in_space_views(&views),
+            BTreeSet::from([
+                "14442C10D13EABCE00".to_owned(),
+                "18443010C1E5D40F00".to_owned()
+            ])
+        );
+        assert_eq!(state.devices_in_space_views(&views[1..]).len(), 1);
+    }
+
+    #[test]
+    fn test_depth_entity_path_follows_alignment() {
+        for align in [BoardSocket::RGB, BoardSocket::RIGHT] {
+            let depth = DepthConfig {
+                align,
+                ..Default::default()
+            };
+            assert_eq!(
+                DEPTHAI_ENTITY_HASHES.get(&depth.entity_path().hash()),
+                Some(&ChannelId::DepthImage)
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_depth() {
+        let config = DeviceConfig::default();
+        assert_eq!(config.validate_depth(), Ok(()));
+
+        let mut disabled = config.clone();
+        disabled.right_camera.enabled = false;
+        assert_eq!(
+            disabled.validate_depth(),
+            Err(ConfigError::MonoCameraDisabled)
+        );
+
+        let mut same_sockets = config.clone();
+        same_sockets.right_camera.board_socket = same_sockets.left_camera.board_socket;
+        assert_eq!(
+            same_sockets.validate_depth(),
+            Err(ConfigError::SameStereoSockets)
+        );
+
+        let mut mismatched_resolution = config.clone();
+        mismatched_resolution.right_camera.resolution = MonoCameraResolution::THE_800_P;
+        assert_eq!(
+            mismatched_resolution.validate_depth(),
+            Err(ConfigError::MonoResolutionMismatch)
+        );
+
+        let mut mismatched_fps = config;
+        mismatched_fps.left_camera.fps = 15;
+        assert_eq!(
+            mismatched_fps.validate_depth(),
+            Err(ConfigError::MonoFpsMismatch)
+        );
+    }
+
+    #[test]
+    fn test_validation_replies_match_requests() {
+        let (mut state, backend, sent) = connected_state();
+        let mut staged = DeviceConfig::default();
+        staged.right_camera.orientation = CameraImageOrientation::ROTATE_180;
+        state.device_config.stage(staged);
+        state.validate_staged_config();
+        assert_eq!(sent_kinds(&sent), vec!["ValidateConfig"]);
+        // Checked the way it'd be applied.
+        let staged = state.device_config.staged.as_ref().unwrap();
+        assert_eq!(
+            staged.right_camera.orientation,
+            staged.left_camera.orientation
+        );
+
+        // A late reply to an earlier request says nothing about this one.
+        let stale = serde_json::json!({"type": "ValidateConfig", "data": {"request_id": 0}});
+        receive(&mut state, &backend, stale);
+        assert!(state.device_config.validation.is_none());
+
+        let id = state.device_config.validation_request_id;
+        let unreadable =
+            serde_json::json!({"type": "ValidateConfig", "data": {"request_id": id, "errors": 1}});
+        receive(&mut state, &backend, unreadable);
+        assert!(!state.device_config.validation.as_ref().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_config_is_not_applied() {
+        let (mut state, _, sent) = connected_state();
+        let mut depth = DepthConfig::default();
+        depth.filters.threshold_min = depth.filters.threshold_max;
+        let mut config = DeviceConfig {
+            depth_enabled: true,
+            depth: Some(depth),
+            ..Default::default()
+        };
+        state.set_device_config(&mut config);
+        assert_eq!(state.device_config.config, DeviceConfig::default());
+        assert!(sent_kinds(&sent).is_empty());
+
+        // Once fixed, the same config goes through.
+        depth.filters.threshold_min = 0;
+        config.depth = Some(depth);
+        state.set_device_config(&mut config);
+        assert_eq!(state.device_config.config, config);
+        assert_eq!(sent_kinds(&sent), vec!["Pipeline"]);
+    }
+
+    #[test]
+    fn test_identical_config_is_applied() {
+        // Nothing runs until the backend says so.
+        let state = DeviceConfigState::default();
+        assert!(!state.is_applied(&DeviceConfig::default()));
+
+        let (mut state, backend, sent) = connected_state();
+        state.update(1.0, true);
+        sent.try_iter().for_each(drop);
+        let mut changed = DeviceConfig::default();
+        changed.color_camera.fps = 15;
+        state.set_device_config(&mut changed.clone());
+        assert_eq!(sent_kinds(&sent), vec!["Pipeline"]);
+
+        // It's on its way already.
+        state.set_device_config(&mut changed.clone());
+        assert!(sent_kinds(&sent).is_empty());
+        assert!(!state.device_config.is_applied(&changed));
+
+        let running = serde_json::json!({"type": "Pipeline", "data": changed});
+        receive(&mut state, &backend, running);
+        sent.try_iter().for_each(drop);
+        assert!(state.device_config.is_applied(&changed));
+
+        // The pipeline already runs with it, restarting it would only drop frames.
+        state.set_device_config(&mut changed.clone());
+        assert!(sent_kinds(&sent).is_empty());
+
+        // Unless starting it failed, then applying it again is how the user retries.
+        state.last_error = Some(Error::default());
+        state.set_device_config(&mut changed.clone());
+        assert_eq!(sent_kinds(&sent), vec!["Pipeline"]);
+    }
+
+    #[test]
+    fn test_color_preview_is_opt_in() {
+        let (mut state, _, _) = connected_state();
+        state.only_subscribe_to_visible = false;
+        state.set_subscriptions_from_space_views(Vec::new());
+        assert!(state.subscriptions.contains(&ChannelId::ColorImage));
+        assert!(!state.subscriptions.contains(&ChannelId::ColorPreview));
+
+        state.show_color_preview = true;
+        state.set_subscriptions_from_space_views(Vec::new());
+        assert!(state.subscriptions.contains(&ChannelId::ColorPreview));
+    }
+
+    #[test]
+    fn test_subscribed_cameras_are_kept() {
+        let remove_channels = channels_to_remove(&DeviceConfig::default(), &all_subscriptions());
+        for channel in CAMERA_CHANNELS {
+            assert!(!remove_channels.contains(&channel));
+        }
+    }
+
+    #[test]
+    fn test_backend_hello_compatibility() {
+        let hello: Hello =
+            serde_json::from_str(r#"{"backend_version": "0.1.0", "api_version": 1}"#).unwrap();
+        assert!(hello.is_compatible());
+
+        // Backends from before the handshake don't send an api version.
+        let hello: Hello = serde_json::from_str(r#"{"backend_version": "0.0.1"}"#).unwrap();
+        assert!(!hello.is_compatible());
+    }
+
+    #[test]
+    fn test_copied_config_json_pastes_back() {
+        let mut config = DeviceConfig::default();
+        config.depth = Some(DepthConfig::default());
+        config.color_camera.fps = 15;
+        let json = serde_json::to_string_pretty(&config).unwrap();
+        assert_eq!(serde_json::from_str::<DeviceConfig>(&json).unwrap(), config);
+    }
+}
@@ -1,3 +1,5 @@
 mod api;
 pub mod depthai;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recording;
 mod ws;
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use instant::Instant;
+use re_log_encoding::{FileSink, FileSinkError};
+use re_log_types::LogMsg;
+
+/// Writes the incoming device streams to an `.rrd` file, the file is finished when this is dropped.
+pub struct StreamRecording {
+    sink: FileSink,
+    pub path: PathBuf,
+    start: Instant,
+}
+
+impl StreamRecording {
+    /// `begin_msg` is the [`LogMsg::BeginRecordingMsg`] of the recording the streams go to,
+    /// without it the file can't be opened on its own.
+    pub fn new(path: PathBuf, begin_msg: Option<LogMsg>) -> Result<Self, FileSinkError> {
+        let sink = FileSink::new(path.clone())?;
+        if let Some(begin_msg) = begin_msg {
+            sink.send(begin_msg);
+        }
+        Ok(Self {
+            sink,
+            path,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn record(&self, msg: &LogMsg) {
+        self.sink.send(msg.clone());
+    }
+
+    pub fn duration(&self) -> std::time::Duration {
+        self.start.elapsed()
+    }
+
+    /// Size of the file on disk, lags behind a little as the writes are compressed in the background.
+    pub fn size_bytes(&self) -> u64 {
+        std::fs::metadata(&self.path).map_or(0, |metadata| metadata.len())
+    }
+}
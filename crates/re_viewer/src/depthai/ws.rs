@@ -3,18 +3,24 @@ use ewebsock::{WsEvent, WsMessage};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::ControlFlow;
-use std::process::exit;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 
+use super::api::{PipelineRequest, ValidateConfigRequest};
 use super::depthai;
 
+/// How long shutting down waits for the queued messages to reach the backend.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(500);
+
 // TODO:(filip) make this try to reconnect until a successful connection
 async fn spawn_ws_client(
     recv_tx: crossbeam_channel::Sender<WsMessage>,
     send_rx: crossbeam_channel::Receiver<WsMessage>,
     shutdown: Arc<AtomicBool>,
     connected: Arc<AtomicBool>,
+    verbose: Arc<AtomicBool>,
+    abandoned: Arc<AtomicBool>,
 ) {
     let (error_tx, error_rx) = crossbeam_channel::unbounded();
     // Retry connection until successful
@@ -37,13 +43,16 @@ async fn spawn_ws_client(
                         ControlFlow::Continue(())
                     }
                     WsEvent::Error(e) => {
-                        // re_log::info!("Websocket Error: {:?}", e);
+                        if connected.load(std::sync::atomic::Ordering::SeqCst) {
+                            re_log::info!("Websocket error: {e}");
+                        }
                         connected.store(false, std::sync::atomic::Ordering::SeqCst);
                         error_tx.send(e);
                         ControlFlow::Break(())
                     }
                     WsEvent::Closed => {
-                        // re_log::info!("Websocket Closed");
+                        re_log::info!("Websocket closed");
+                        connected.store(false, std::sync::atomic::Ordering::SeqCst);
                         error_tx.send(String::from("Websocket Closed"));
                         ControlFlow::Break(())
                     }
@@ -53,12 +62,27 @@ async fn spawn_ws_client(
         .as_mut()
         {
             while error_rx.is_empty() {
+                if abandoned.load(std::sync::atomic::Ordering::SeqCst) {
+                    re_log::debug!("Abandoning websocket client");
+                    return;
+                }
                 if shutdown.load(std::sync::atomic::Ordering::SeqCst) {
+                    // Flush what's still queued, e.g. the unsubscribe sent on shutdown,
+                    // so the backend stops streaming and releases the device.
+                    for message in send_rx.try_iter() {
+                        log_message("Sending", &message, &verbose);
+                        sender.send(message);
+                    }
+                    // Closing sends what's queued first, the socket reports when it's closed.
+                    sender.close();
+                    if error_rx.recv_timeout(SHUTDOWN_TIMEOUT).is_err() {
+                        re_log::debug!("Timed out closing the websocket");
+                    }
                     re_log::debug!("Shutting down websocket client");
-                    exit(0);
+                    return;
                 }
                 if let Ok(message) = send_rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                    re_log::debug!("Sending message: {:?}", message);
+                    log_message("Sending", &message, &verbose);
                     sender.send(message);
                 }
             }
@@ -70,9 +94,21 @@ async fn spawn_ws_client(
         }
         if shutdown.load(std::sync::atomic::Ordering::SeqCst) {
             re_log::debug!("Shutting down websocket client");
-            exit(0);
+            return;
         }
         std::thread::sleep(std::time::Duration::from_secs(1));
+        if abandoned.load(std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+    }
+}
+
+/// Messages are only logged when asked for, they'd flood the log at the rate the backend streams.
+fn log_message(what: &str, message: &impl fmt::Debug, verbose: &AtomicBool) {
+    if verbose.load(std::sync::atomic::Ordering::Relaxed) {
+        re_log::info!("{what}: {message:?}");
+    } else {
+        re_log::trace!("{what}: {message:?}");
     }
 }
 
@@ -82,7 +118,29 @@ pub enum WsMessageData {
     Devices(Vec<depthai::DeviceId>),
     Device(depthai::Device),
     Pipeline(depthai::DeviceConfig),
+    /// What the viewer sends to create a pipeline, the backend replies with a [`Self::Pipeline`].
+    #[serde(rename = "Pipeline", skip_deserializing)]
+    PipelineRequest(PipelineRequest),
+    CaptureStill(depthai::Device),
+    Stats(depthai::DeviceStats),
+    ExposureReadback(depthai::ExposureReadback),
+    Log(depthai::LogEntry),
+    Hello(depthai::Hello),
+    /// A DepthAI calibration json overriding the device calibration, `null` to go back to it.
+    Calibration(serde_json::Value),
+    /// Ask for a single frame on each of these channels, used to step through frames while paused.
+    RequestFrame(Vec<depthai::ChannelId>),
+    /// Close every handle to the device, including stale ones from earlier sessions, and open it again.
+    ForceRelease(depthai::Device),
     Error(depthai::Error),
+    /// The backend's verdict on a config sent with [`Self::ValidateConfigRequest`].
+    ValidateConfig(depthai::ConfigValidation),
+    /// Check a pipeline without starting it, the backend replies with a [`Self::ValidateConfig`].
+    #[serde(rename = "ValidateConfig", skip_deserializing)]
+    ValidateConfigRequest(ValidateConfigRequest),
+    /// The data of a [`WsMessageType::Unknown`] message, never sent.
+    #[serde(skip)]
+    Unknown(serde_json::Value),
 }
 
 #[derive(Deserialize, Serialize, fmt::Debug)]
@@ -91,7 +149,19 @@ pub enum WsMessageType {
     Devices,
     Device,
     Pipeline,
+    CaptureStill,
+    Stats,
+    ExposureReadback,
+    Log,
+    Hello,
+    Calibration,
+    RequestFrame,
+    ForceRelease,
     Error,
+    ValidateConfig,
+    /// A type this viewer doesn't know, e.g. from a newer backend, with the name it was sent as.
+    #[serde(skip)]
+    Unknown(String),
 }
 
 impl Default for WsMessageType {
@@ -116,12 +186,14 @@ impl<'de> Deserialize<'de> for BackWsMessage {
         #[derive(Deserialize)]
         pub struct Message {
             #[serde(rename = "type")]
-            pub kind: WsMessageType,
+            pub kind: String,
             pub data: serde_json::Value,
         }
 
         let message = Message::deserialize(deserializer)?;
-        let data = match message.kind {
+        let kind = serde_json::from_value(serde_json::Value::String(message.kind.clone()))
+            .unwrap_or(WsMessageType::Unknown(message.kind));
+        let data = match &kind {
             WsMessageType::Subscriptions => WsMessageData::Subscriptions(
                 serde_json::from_value(message.data).unwrap_or_default(),
             ),
@@ -135,14 +207,52 @@ impl<'de> Deserialize<'de> for BackWsMessage {
                 WsMessageData::Pipeline(serde_json::from_value(message.data).unwrap())
                 // TODO(filip) change to unwrap_or_default when pipeline config api is more stable
             }
+            WsMessageType::CaptureStill => WsMessageData::CaptureStill(
+                serde_json::from_value(message.data).unwrap_or_default(),
+            ),
+            WsMessageType::Stats => {
+                WsMessageData::Stats(serde_json::from_value(message.data).unwrap_or_default())
+            }
+            WsMessageType::ExposureReadback => WsMessageData::ExposureReadback(
+                serde_json::from_value(message.data).unwrap_or_default(),
+            ),
+            WsMessageType::Log => {
+                WsMessageData::Log(serde_json::from_value(message.data).unwrap_or_default())
+            }
+            WsMessageType::Hello => {
+                WsMessageData::Hello(serde_json::from_value(message.data).unwrap_or_default())
+            }
+            WsMessageType::Calibration => {
+                WsMessageData::Calibration(serde_json::from_value(message.data).unwrap_or_default())
+            }
+            WsMessageType::RequestFrame => WsMessageData::RequestFrame(
+                serde_json::from_value(message.data).unwrap_or_default(),
+            ),
+            WsMessageType::ForceRelease => WsMessageData::ForceRelease(
+                serde_json::from_value(message.data).unwrap_or_default(),
+            ),
             WsMessageType::Error => {
                 WsMessageData::Error(serde_json::from_value(message.data).unwrap_or_default())
             }
+            WsMessageType::ValidateConfig => {
+                let request_id = message
+                    .data
+                    .get("request_id")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|id| u32::try_from(id).ok())
+                    .unwrap_or_default();
+                // A verdict that can't be read doesn't mean the config is fine.
+                WsMessageData::ValidateConfig(serde_json::from_value(message.data).unwrap_or_else(
+                    |err| depthai::ConfigValidation {
+                        request_id,
+                        errors: vec![format!("Couldn't read the backend's verdict: {err}")],
+                        ..Default::default()
+                    },
+                ))
+            }
+            WsMessageType::Unknown(_) => WsMessageData::Unknown(message.data),
         };
-        Ok(Self {
-            kind: message.kind,
-            data,
-        })
+        Ok(Self { kind, data })
     }
 }
 
@@ -161,6 +271,10 @@ pub struct WebSocket {
     shutdown: Arc<AtomicBool>,
     task: tokio::task::JoinHandle<()>,
     pub connected: Arc<AtomicBool>,
+    /// Log every sent and received message, see [`Self::set_verbose`].
+    verbose: Arc<AtomicBool>,
+    /// Tells the client task to stop without shutting down the app, see [`Self::reconnect`].
+    abandoned: Arc<AtomicBool>,
 }
 
 impl Default for WebSocket {
@@ -178,6 +292,10 @@ impl WebSocket {
         let shutdown_clone = shutdown.clone();
         let connected = Arc::new(AtomicBool::new(false));
         let connected_clone = connected.clone();
+        let verbose = Arc::new(AtomicBool::new(false));
+        let verbose_clone = verbose.clone();
+        let abandoned = Arc::new(AtomicBool::new(false));
+        let abandoned_clone = abandoned.clone();
         let mut task = None;
         if let Ok(handle) = tokio::runtime::Handle::try_current() {
             re_log::debug!("Using current tokio runtime");
@@ -186,6 +304,8 @@ impl WebSocket {
                 send_rx,
                 shutdown_clone,
                 connected_clone,
+                verbose_clone,
+                abandoned_clone,
             )));
         } else {
             re_log::debug!("Creating new tokio runtime");
@@ -198,6 +318,8 @@ impl WebSocket {
                         send_rx,
                         shutdown_clone,
                         connected_clone,
+                        verbose_clone,
+                        abandoned_clone,
                     )),
             );
         }
@@ -207,22 +329,58 @@ impl WebSocket {
             shutdown,
             task: task.unwrap(),
             connected,
+            verbose,
+            abandoned,
         }
     }
 
+    /// Replace the client with a fresh one, for when the connection to the backend died.
+    ///
+    /// Messages that weren't sent yet are dropped.
+    pub fn reconnect(&mut self) {
+        re_log::info!("Reconnecting to the backend");
+        self.abandoned
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        self.task.abort();
+        let verbose = self.verbose.load(std::sync::atomic::Ordering::Relaxed);
+        *self = Self::new();
+        self.set_verbose(verbose);
+    }
+
+    /// Log every message to and from the backend at info level, for debugging the protocol.
+    pub fn set_verbose(&self, verbose: bool) {
+        self.verbose
+            .store(verbose, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Stop the client once it sent what's queued, waiting for that for at most [`SHUTDOWN_TIMEOUT`].
     pub fn shutdown(&mut self) {
         self.shutdown
             .store(true, std::sync::atomic::Ordering::SeqCst);
+        let deadline = instant::Instant::now() + SHUTDOWN_TIMEOUT;
+        while !self.task.is_finished() && instant::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
     }
 
-    pub fn receive(&self) -> Option<BackWsMessage> {
+    /// The next message from the backend, along with the text it was received as.
+    pub fn receive(&self) -> Option<(BackWsMessage, String)> {
         if let Ok(message) = self.receiver.try_recv() {
             match message {
                 WsMessage::Text(text) => {
-                    re_log::debug!("Received: {:?}", text);
+                    log_message("Received", &text, &self.verbose);
                     match serde_json::from_str::<BackWsMessage>(&text.as_str()) {
+                        Ok(BackWsMessage {
+                            kind: WsMessageType::Unknown(kind),
+                            ..
+                        }) => {
+                            re_log::warn_once!(
+                                "Skipping {kind:?} messages from the backend, this viewer doesn't know them"
+                            );
+                            return None;
+                        }
                         Ok(back_message) => {
-                            return Some(back_message);
+                            return Some((back_message, text));
                         }
                         Err(error) => {
                             re_log::error!("Error: {:?}", error);
@@ -235,6 +393,29 @@ impl WebSocket {
         }
         None
     }
+    /// A client that pretends to be connected, for testing what the viewer sends.
+    ///
+    /// Returns where to put what the backend should send and where what the viewer sends ends up.
+    #[cfg(test)]
+    pub fn loopback() -> (Self, Sender<WsMessage>, Receiver<WsMessage>) {
+        let (recv_tx, recv_rx) = crossbeam_channel::unbounded();
+        let (send_tx, send_rx) = crossbeam_channel::unbounded();
+        let task = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .spawn(async {});
+        let websocket = Self {
+            receiver: recv_rx,
+            sender: send_tx,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            task,
+            connected: Arc::new(AtomicBool::new(true)),
+            verbose: Arc::new(AtomicBool::new(false)),
+            abandoned: Arc::new(AtomicBool::new(false)),
+        };
+        (websocket, recv_tx, send_rx)
+    }
+
     pub fn send(&self, message: String) {
         self.sender.send(WsMessage::Text(message));
         // TODO(filip): This is a hotfix for the websocket not sending the message
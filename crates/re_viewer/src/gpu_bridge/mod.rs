@@ -43,6 +43,20 @@ pub fn range(tensor_stats: &crate::misc::caches::TensorStats) -> Result<[f32; 2]
     }
 }
 
+/// Convert the color mapper picked in the ui to what `re_renderer` understands.
+pub fn re_renderer_colormap(color_mapper: re_data_store::ColorMapper) -> re_renderer::Colormap {
+    match color_mapper {
+        re_data_store::ColorMapper::Colormap(colormap) => match colormap {
+            re_data_store::Colormap::Grayscale => re_renderer::Colormap::Grayscale,
+            re_data_store::Colormap::Turbo => re_renderer::Colormap::Turbo,
+            re_data_store::Colormap::Viridis => re_renderer::Colormap::Viridis,
+            re_data_store::Colormap::Plasma => re_renderer::Colormap::Plasma,
+            re_data_store::Colormap::Magma => re_renderer::Colormap::Magma,
+            re_data_store::Colormap::Inferno => re_renderer::Colormap::Inferno,
+        },
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 pub fn viewport_resolution_in_pixels(clip_rect: egui::Rect, pixels_from_point: f32) -> [u32; 2] {
@@ -24,6 +24,9 @@ use super::try_get_or_create_texture;
 /// This will only upload the tensor if it isn't on the GPU already.
 ///
 /// `tensor_stats` is used for determining the range of the texture.
+///
+/// `color_mapper` is used for depth tensors, which default to turbo if it isn't set,
+/// and for single channel images, which default to grayscale.
 // TODO(emilk): allow user to specify the range in ui.
 pub fn tensor_to_gpu(
     render_ctx: &mut RenderContext,
@@ -31,6 +34,7 @@ pub fn tensor_to_gpu(
     tensor: &Tensor,
     tensor_stats: &TensorStats,
     annotations: &crate::ui::Annotations,
+    color_mapper: Option<re_data_store::ColorMapper>,
 ) -> anyhow::Result<ColormappedTexture> {
     crate::profile_function!(format!(
         "meaning: {:?}, dtype: {}, shape: {:?}",
@@ -43,13 +47,13 @@ pub fn tensor_to_gpu(
 
     match tensor.meaning {
         TensorDataMeaning::Unknown => {
-            color_tensor_to_gpu(render_ctx, debug_name, tensor, tensor_stats)
+            color_tensor_to_gpu(render_ctx, debug_name, tensor, tensor_stats, color_mapper)
         }
         TensorDataMeaning::ClassId => {
             class_id_tensor_to_gpu(render_ctx, debug_name, tensor, tensor_stats, annotations)
         }
         TensorDataMeaning::Depth => {
-            depth_tensor_to_gpu(render_ctx, debug_name, tensor, tensor_stats)
+            depth_tensor_to_gpu(render_ctx, debug_name, tensor, tensor_stats, color_mapper)
         }
     }
 }
@@ -62,6 +66,7 @@ fn color_tensor_to_gpu(
     debug_name: &str,
     tensor: &Tensor,
     tensor_stats: &TensorStats,
+    color_mapper: Option<re_data_store::ColorMapper>,
 ) -> anyhow::Result<ColormappedTexture> {
     let texture_handle = try_get_or_create_texture(render_ctx, hash(tensor.id()), || {
         let [height, width, depth] = height_width_depth(tensor)?;
@@ -112,8 +117,11 @@ fn color_tensor_to_gpu(
     };
 
     let color_mapper = if texture_format.describe().components == 1 {
-        // Single-channel images = luminance = grayscale
-        Some(ColorMapper::Function(re_renderer::Colormap::Grayscale))
+        // Single-channel images = luminance = grayscale, unless asked otherwise
+        Some(ColorMapper::Function(color_mapper.map_or(
+            re_renderer::Colormap::Grayscale,
+            crate::gpu_bridge::re_renderer_colormap,
+        )))
     } else {
         None
     };
@@ -202,6 +210,7 @@ fn depth_tensor_to_gpu(
     debug_name: &str,
     tensor: &Tensor,
     tensor_stats: &TensorStats,
+    color_mapper: Option<re_data_store::ColorMapper>,
 ) -> anyhow::Result<ColormappedTexture> {
     let [_height, _width, depth] = height_width_depth(tensor)?;
     anyhow::ensure!(
@@ -219,7 +228,10 @@ fn depth_tensor_to_gpu(
         texture,
         range: [min as f32, max as f32],
         gamma: 1.0,
-        color_mapper: Some(ColorMapper::Function(re_renderer::Colormap::Turbo)),
+        color_mapper: Some(ColorMapper::Function(color_mapper.map_or(
+            re_renderer::Colormap::Turbo,
+            crate::gpu_bridge::re_renderer_colormap,
+        ))),
     })
 }
 
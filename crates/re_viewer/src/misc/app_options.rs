@@ -10,6 +10,11 @@ pub struct AppOptions {
     /// Show milliseconds, RAM usage, etc.
     pub show_metrics: bool,
 
+    /// How often to ask the backend for connected devices while none is selected, in seconds.
+    ///
+    /// Polling is paused while the viewer window isn't focused.
+    pub device_poll_interval: f32,
+
     /// Zoom factor, independent of OS points_per_pixel setting.
     ///
     /// At every frame we check the OS reported scaling (i.e. points_per_pixel)
@@ -25,6 +30,9 @@ pub struct AppOptions {
 
     /// Displays an overlay for debugging picking.
     pub show_picking_debug_overlay: bool,
+
+    /// Log every message exchanged with the depthai backend.
+    pub log_backend_messages: bool,
 }
 
 impl Default for AppOptions {
@@ -37,6 +45,8 @@ impl Default for AppOptions {
 
             show_metrics: false,
 
+            device_poll_interval: 2.0,
+
             #[cfg(not(target_arch = "wasm32"))]
             zoom_factor: 1.0,
 
@@ -44,6 +54,8 @@ impl Default for AppOptions {
             experimental_space_view_screenshots: false,
 
             show_picking_debug_overlay: false,
+
+            log_backend_messages: false,
         }
     }
 }
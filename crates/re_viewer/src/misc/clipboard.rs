@@ -21,6 +21,17 @@ impl Clipboard {
         }
     }
 
+    pub fn get_text(&mut self) -> Option<String> {
+        let clipboard = self.arboard.as_mut()?;
+        match clipboard.get_text() {
+            Ok(text) => Some(text),
+            Err(err) => {
+                re_log::error!("Failed to read the clipboard: {err}");
+                None
+            }
+        }
+    }
+
     pub fn set_image(&mut self, size: [usize; 2], rgba_unmultiplied: &[u8]) {
         let [width, height] = size;
         assert_eq!(width * height * 4, rgba_unmultiplied.len());
@@ -45,11 +45,15 @@ impl<'a> ViewerContext<'a> {
         space_view_id: Option<SpaceViewId>,
         entity_path: &EntityPath,
     ) -> egui::Response {
+        let text = self
+            .depthai_state
+            .entity_display_name(entity_path)
+            .map_or_else(|| entity_path.to_string(), ToOwned::to_owned);
         self.instance_path_button_to(
             ui,
             space_view_id,
             &InstancePath::entity_splat(entity_path.clone()),
-            entity_path.to_string(),
+            text,
         )
     }
 
@@ -0,0 +1,420 @@
+use egui::plot::{Corner, Legend, Line, Plot, PlotPoints};
+use egui::util::History;
+use itertools::Itertools as _;
+use re_format::format_bytes;
+use re_log_types::{external::arrow2, ArrowMsg, LogMsg, Time, Timeline};
+use std::collections::HashMap;
+
+use crate::depthai::depthai;
+
+use super::plot_following::PlotFollowing;
+
+// ----------------------------------------------------------------------------
+
+/// Roughly what a USB2 connection to the device sustains in practice, in MB/s.
+const USB2_CAPACITY_MB: f32 = 35.0;
+
+/// Warn when the received bandwidth goes above this fraction of the link capacity.
+const LINK_WARNING_FRACTION: f32 = 0.8;
+
+/// Rolling receive stats of a single device stream.
+struct StreamStats {
+    /// End-to-end latency in seconds, keyed by the time the message arrived.
+    ///
+    /// The arrival times double as the frame times, so this also gives us the FPS.
+    latency: History<f32>,
+
+    bytes_since_sample: u64,
+
+    /// Received bytes per second, as of the last sample.
+    bytes_per_sec: u64,
+}
+
+impl Default for StreamStats {
+    fn default() -> Self {
+        Self {
+            latency: History::new(0..300, 2.0),
+            bytes_since_sample: 0,
+            bytes_per_sec: 0,
+        }
+    }
+}
+
+impl StreamStats {
+    fn fps(&self) -> Option<f32> {
+        self.latency.rate()
+    }
+
+    fn latency_sec(&self) -> Option<f32> {
+        self.latency.average()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Shows how much data the viewer is receiving from the device.
+pub struct BandwidthPanel {
+    /// Received bytes per second, sampled once every second.
+    history: History<u64>,
+
+    /// Bytes received since the last sample was added to the history.
+    bytes_since_sample: u64,
+
+    /// Bytes received since we connected to the current device.
+    total_bytes: u64,
+
+    last_sample: instant::Instant,
+    start_time: instant::Instant,
+
+    /// Warn when receiving more than [`LINK_WARNING_FRACTION`] of this, in MB/s.
+    link_capacity_mb: f32,
+
+    following: PlotFollowing<u64>,
+
+    /// FPS and latency of every stream we've received data for since connecting.
+    streams: HashMap<depthai::ChannelId, StreamStats>,
+
+    /// Frames the backend dropped per stream, as last reported in the device stats.
+    dropped_frames: HashMap<depthai::ChannelId, u64>,
+
+    /// Used to detect device changes and connection resets.
+    device_id: depthai::DeviceId,
+    connected: bool,
+
+    /// Suggest a config that fits in [`Self::budget_mb`].
+    fit_to_budget: bool,
+
+    /// In MB/s.
+    budget_mb: f32,
+}
+
+impl Default for BandwidthPanel {
+    fn default() -> Self {
+        Self {
+            history: History::new(0..1000, 60.0),
+            bytes_since_sample: 0,
+            total_bytes: 0,
+            last_sample: instant::Instant::now(),
+            start_time: instant::Instant::now(),
+            link_capacity_mb: USB2_CAPACITY_MB,
+            following: Default::default(),
+            streams: Default::default(),
+            dropped_frames: Default::default(),
+            device_id: depthai::DeviceId::default(),
+            connected: false,
+            fit_to_budget: false,
+            budget_mb: USB2_CAPACITY_MB * LINK_WARNING_FRACTION,
+        }
+    }
+}
+
+impl BandwidthPanel {
+    /// Call for every received message.
+    pub fn on_log_msg(&mut self, msg: &LogMsg) {
+        if let LogMsg::ArrowMsg(_, arrow_msg) = msg {
+            let num_bytes = arrow_msg
+                .chunk
+                .arrays()
+                .iter()
+                .map(|array| arrow2::compute::aggregate::estimated_bytes_size(array.as_ref()))
+                .sum::<usize>() as u64;
+            self.bytes_since_sample += num_bytes;
+            self.total_bytes += num_bytes;
+
+            self.on_arrow_msg(arrow_msg, num_bytes);
+        }
+    }
+
+    fn on_arrow_msg(&mut self, arrow_msg: &ArrowMsg, num_bytes: u64) {
+        let now = self.start_time.elapsed().as_secs_f64();
+        let latency_sec =
+            arrow_msg
+                .timepoint_max
+                .get(&Timeline::log_time())
+                .map_or(0.0, |log_time| {
+                    let latency_ns = Time::now().nanos_since_epoch() - log_time.as_i64();
+                    latency_ns.max(0) as f32 / 1e9
+                });
+
+        for channel in depthai::arrow_msg_entity_paths(arrow_msg)
+            .iter()
+            .filter_map(depthai::channel_from_entity_path)
+            .unique()
+        {
+            let stats = self.streams.entry(channel).or_default();
+            stats.latency.add(now, latency_sec);
+            stats.bytes_since_sample += num_bytes;
+        }
+    }
+
+    /// Call once per frame
+    pub fn update(&mut self, depthai_state: &mut depthai::State) {
+        crate::profile_function!();
+
+        let connected = depthai_state
+            .backend_comms
+            .ws
+            .connected
+            .load(std::sync::atomic::Ordering::SeqCst);
+        if depthai_state.selected_device.id != self.device_id || connected != self.connected {
+            self.reset();
+            depthai_state.channel_bandwidth.clear();
+            self.device_id = depthai_state.selected_device.id.clone();
+            self.connected = connected;
+        }
+
+        if let Some(stats) = &depthai_state.device_stats {
+            self.dropped_frames.clone_from(&stats.dropped_frames);
+        }
+
+        let elapsed = self.last_sample.elapsed().as_secs_f64();
+        if elapsed >= 1.0 {
+            self.history.add(
+                self.start_time.elapsed().as_secs_f64(),
+                (self.bytes_since_sample as f64 / elapsed) as u64,
+            );
+            self.bytes_since_sample = 0;
+            for stats in self.streams.values_mut() {
+                stats.bytes_per_sec = (stats.bytes_since_sample as f64 / elapsed) as u64;
+                stats.bytes_since_sample = 0;
+            }
+            depthai_state.channel_bandwidth = self
+                .streams
+                .iter()
+                .map(|(channel, stats)| (*channel, stats.bytes_per_sec))
+                .collect();
+            self.last_sample = instant::Instant::now();
+        }
+
+        let now = self.start_time.elapsed().as_secs_f64();
+        for stats in self.streams.values_mut() {
+            stats.latency.flush(now);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+        self.bytes_since_sample = 0;
+        self.total_bytes = 0;
+        self.last_sample = instant::Instant::now();
+        self.streams.clear();
+        self.dropped_frames.clear();
+    }
+
+    /// Set when we're receiving close to what the link to the device can handle,
+    /// with a suggestion of what to drop.
+    pub fn link_warning(&self) -> Option<String> {
+        let bytes_per_sec = self.history.latest()? as f32;
+        let capacity = self.link_capacity_mb * 1e6;
+        if bytes_per_sec <= capacity * LINK_WARNING_FRACTION {
+            return None;
+        }
+
+        let mut warning = format!(
+            "Receiving {}/s, {:.0}% of the {}/s link capacity. Frames will start dropping.",
+            format_bytes(bytes_per_sec as _),
+            100.0 * bytes_per_sec / capacity,
+            format_bytes(capacity as _),
+        );
+        if let Some((channel, _)) = self
+            .streams
+            .iter()
+            .max_by_key(|(_, stats)| stats.bytes_per_sec)
+        {
+            if *channel == depthai::ChannelId::ColorImage {
+                warning.push_str(
+                    "\nLower the color camera ISP scale, encode it as MJPEG, stop streaming it or frame with the preview.",
+                );
+            } else {
+                warning.push_str(&format!("\nConsider stopping the {channel:?} stream."));
+            }
+        }
+        Some(warning)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+        crate::profile_function!();
+
+        // We show realtime stats, so keep showing the latest!
+        ui.ctx().request_repaint();
+
+        if let Some(warning) = self.link_warning() {
+            egui::Frame::none()
+                .fill(ui.visuals().faint_bg_color)
+                .inner_margin(egui::Margin::same(4.0))
+                .show(ui, |ui| {
+                    ui.colored_label(ui.visuals().error_fg_color, warning);
+                });
+        }
+
+        ui.strong("Bandwidth");
+        ui.label(format!(
+            "Received since connect: {}",
+            format_bytes(self.total_bytes as _)
+        ));
+        ui.horizontal(|ui| {
+            ui.label("Link capacity:");
+            ui.add(
+                egui::DragValue::new(&mut self.link_capacity_mb)
+                    .clamp_range(1.0..=10_000.0)
+                    .suffix(" MB/s"),
+            )
+            .on_hover_text("USB2 manages about 35 MB/s, USB3 and PoE considerably more.");
+        });
+        self.following.ui(ui, &self.history);
+        self.plot(ui);
+
+        ui.separator();
+        self.budget_ui(ui, depthai_state);
+
+        ui.separator();
+        ui.strong("Streams");
+        self.streams_ui(ui);
+    }
+
+    /// Suggests what to lower to stay within a bandwidth budget, applied on confirmation.
+    fn budget_ui(&mut self, ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.fit_to_budget, "Fit to budget:")
+                .on_hover_text("Suggest a device config that stays within this bandwidth");
+            ui.add_enabled(
+                self.fit_to_budget,
+                egui::DragValue::new(&mut self.budget_mb)
+                    .clamp_range(1.0..=10_000.0)
+                    .suffix(" MB/s"),
+            );
+        });
+        if !self.fit_to_budget {
+            return;
+        }
+
+        let bandwidth = self
+            .streams
+            .iter()
+            .map(|(channel, stats)| (*channel, stats.bytes_per_sec))
+            .collect();
+        let Some(suggestion) = depthai::fit_to_bandwidth_budget(
+            &depthai_state.device_config.config,
+            &bandwidth,
+            (self.budget_mb * 1e6) as u64,
+        ) else {
+            ui.weak("Within budget");
+            return;
+        };
+        if suggestion.actions.is_empty() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "Over budget, but there is nothing left to lower",
+            );
+            return;
+        }
+        ui.label(format!(
+            "Over budget, about {}/s after:",
+            format_bytes(suggestion.bytes_per_sec as _)
+        ));
+        for action in &suggestion.actions {
+            ui.label(format!("• {action}"));
+        }
+        let can_apply = depthai_state.selected_device.id != "" && !depthai_state.playback;
+        if ui
+            .add_enabled(can_apply, egui::Button::new("Apply"))
+            .on_hover_text("Restart the pipeline with the suggested config")
+            .clicked()
+        {
+            for action in &suggestion.actions {
+                re_log::info!("Bandwidth budget: {action}");
+            }
+            let mut config = suggestion.config;
+            depthai_state.set_device_config(&mut config);
+        }
+    }
+
+    fn streams_ui(&self, ui: &mut egui::Ui) {
+        if self.streams.is_empty() {
+            ui.weak("No data received yet");
+            return;
+        }
+
+        egui::Grid::new("stream_stats_grid")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Stream");
+                ui.strong("FPS");
+                ui.strong("Latency");
+                ui.strong("Bandwidth");
+                ui.strong("Dropped").on_hover_text(
+                    "Frames the device dropped because the host didn't keep up with the stream",
+                );
+                ui.end_row();
+
+                for (channel, stats) in self
+                    .streams
+                    .iter()
+                    .sorted_by_key(|(channel, _)| **channel as u8)
+                {
+                    ui.label(format!("{channel:?}"));
+                    ui.label(
+                        stats
+                            .fps()
+                            .map_or("-".to_owned(), |fps| format!("{fps:.1}")),
+                    );
+                    ui.label(
+                        stats
+                            .latency_sec()
+                            .map_or("-".to_owned(), |sec| format!("{:.0} ms", sec * 1e3)),
+                    );
+                    ui.label(format!("{}/s", format_bytes(stats.bytes_per_sec as _)));
+                    let dropped = self
+                        .dropped_frames
+                        .get(channel)
+                        .copied()
+                        .unwrap_or_default();
+                    if dropped > 0 {
+                        ui.colored_label(ui.visuals().error_fg_color, dropped.to_string());
+                    } else {
+                        ui.label("0");
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn plot(&self, ui: &mut egui::Ui) {
+        crate::profile_function!();
+
+        // egui plots don't have axis titles, so they go above the y axis and below the x axis,
+        // and the units go into the tick labels.
+        let plot = Plot::new("bandwidth_history_plot")
+            .min_size(egui::Vec2::splat(200.0))
+            .label_formatter(|name, value| {
+                format!(
+                    "{name}: {}/s\nTime (s): {:.1}",
+                    format_bytes(value.y),
+                    value.x
+                )
+            })
+            .x_axis_formatter(|time, _| format!("{time} s"))
+            .y_axis_formatter(|bytes, _| format!("{}/s", format_bytes(bytes)))
+            .legend(Legend::default().position(Corner::LeftTop))
+            .include_y(0.0);
+        ui.small("Bandwidth");
+        self.following.plot(plot).show(ui, |plot_ui| {
+            plot_ui.line(
+                Line::new(PlotPoints::new(
+                    self.following
+                        .points(&self.history)
+                        .into_iter()
+                        .map(|(time, bytes)| [time, bytes as f64])
+                        .collect_vec(),
+                ))
+                .name("Bandwidth"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.small("Time (s)");
+            });
+        });
+    }
+}
@@ -1,6 +1,7 @@
 use egui::{Color32, Vec2};
 use itertools::Itertools as _;
 
+use re_data_store::DepthUnit;
 use re_log_types::{
     component_types::{ClassId, Tensor, TensorDataMeaning},
     TensorElement,
@@ -60,6 +61,14 @@ fn tensor_ui(
     // Even if not, we will show info about the tensor.
     let tensor_stats = *ctx.cache.tensor_stats(tensor);
     let annotations = annotations(ctx, query, entity_path);
+    // Outside of a space view, show the stream with the color map the user last picked for it.
+    let color_mapper = ctx.depthai_state.entity_color_mapper(entity_path);
+    // Like space views guess it before the user picks one.
+    let depth_unit = if tensor.dtype().is_integer() {
+        DepthUnit::Millimeters
+    } else {
+        DepthUnit::Meters
+    };
     let debug_name = entity_path.to_string();
     let texture_result = crate::gpu_bridge::tensor_to_gpu(
         ctx.render_ctx,
@@ -67,6 +76,7 @@ fn tensor_ui(
         tensor,
         &tensor_stats,
         &annotations,
+        color_mapper,
     )
     .ok();
 
@@ -136,6 +146,8 @@ fn tensor_ui(
                             &tensor_stats,
                             &annotations,
                             tensor.meter,
+                            depth_unit,
+                            color_mapper,
                             &debug_name,
                             image_rect,
                             pointer_pos,
@@ -332,6 +344,8 @@ fn show_zoomed_image_region_tooltip(
     tensor_stats: &TensorStats,
     annotations: &crate::ui::Annotations,
     meter: Option<f32>,
+    depth_unit: DepthUnit,
+    color_mapper: Option<re_data_store::ColorMapper>,
     debug_name: &str,
     image_rect: egui::Rect,
     pointer_pos: egui::Pos2,
@@ -361,6 +375,8 @@ fn show_zoomed_image_region_tooltip(
                         tensor_stats,
                         annotations,
                         meter,
+                        depth_unit,
+                        color_mapper,
                         debug_name,
                         center_texel,
                     );
@@ -404,6 +420,10 @@ pub fn show_zoomed_image_region_area_outline(
 }
 
 /// `meter`: iff this is a depth map, how long is one meter?
+///
+/// `depth_unit`: the unit the depth under the pointer is shown in.
+///
+/// `color_mapper`: the color map the image is shown with, so the zoomed region matches it.
 #[allow(clippy::too_many_arguments)]
 pub fn show_zoomed_image_region(
     render_ctx: &mut re_renderer::RenderContext,
@@ -412,6 +432,8 @@ pub fn show_zoomed_image_region(
     tensor_stats: &TensorStats,
     annotations: &crate::ui::Annotations,
     meter: Option<f32>,
+    depth_unit: DepthUnit,
+    color_mapper: Option<re_data_store::ColorMapper>,
     debug_name: &str,
     center_texel: [isize; 2],
 ) {
@@ -422,6 +444,8 @@ pub fn show_zoomed_image_region(
         tensor_stats,
         annotations,
         meter,
+        depth_unit,
+        color_mapper,
         debug_name,
         center_texel,
     ) {
@@ -438,6 +462,8 @@ fn try_show_zoomed_image_region(
     tensor_stats: &TensorStats,
     annotations: &crate::ui::Annotations,
     meter: Option<f32>,
+    depth_unit: DepthUnit,
+    color_mapper: Option<re_data_store::ColorMapper>,
     debug_name: &str,
     center_texel: [isize; 2],
 ) -> anyhow::Result<()> {
@@ -447,6 +473,7 @@ fn try_show_zoomed_image_region(
         tensor,
         tensor_stats,
         annotations,
+        color_mapper,
     )?;
 
     let Some([height, width, _]) = tensor.image_height_width_channels() else { return Ok(()); };
@@ -490,7 +517,7 @@ fn try_show_zoomed_image_region(
         ui.separator();
 
         ui.vertical(|ui| {
-            tensor_pixel_value_ui(ui, tensor, annotations, [x as _, y as _], meter);
+            tensor_pixel_value_ui(ui, tensor, annotations, [x as _, y as _], meter, depth_unit);
 
             // Show a big sample of the color of the middle texel:
             let (rect, _) =
@@ -522,6 +549,7 @@ fn tensor_pixel_value_ui(
     annotations: &crate::ui::Annotations,
     [x, y]: [u64; 2],
     meter: Option<f32>,
+    depth_unit: DepthUnit,
 ) {
     egui::Grid::new("hovered pixel properties").show(ui, |ui| {
         ui.label("Position:");
@@ -547,16 +575,29 @@ fn tensor_pixel_value_ui(
         }
         if let Some(meter) = meter {
             // This is a depth map
-            if let Some(raw_value) = tensor.get(&[y, x]) {
-                let raw_value = raw_value.as_f64();
-                let meters = raw_value / meter as f64;
-                ui.label("Depth:");
-                if meters < 1.0 {
-                    ui.monospace(format!("{:.1} mm", meters * 1e3));
-                } else {
-                    ui.monospace(format!("{meters:.3} m"));
+            ui.label("Depth:");
+            // Zero is what stereo depth reports for pixels without a match.
+            let meters = tensor
+                .get(&[y, x])
+                .map(|raw_value| raw_value.as_f64())
+                .filter(|raw_value| *raw_value > 0.0 && raw_value.is_finite())
+                .map(|raw_value| raw_value / meter as f64);
+            match meters {
+                Some(meters) => {
+                    // In the unit picked for the entity, so it reads the same across pixels.
+                    let depth = meters * depth_unit.steps_per_meter() as f64;
+                    let decimals = match depth_unit {
+                        DepthUnit::Millimeters => 1,
+                        DepthUnit::Meters => 3,
+                    };
+                    ui.monospace(format!("{depth:.decimals$} {}", depth_unit.symbol()));
+                }
+                None => {
+                    ui.monospace("—")
+                        .on_hover_text("No valid depth at this pixel");
                 }
             }
+            ui.end_row();
         }
     });
 
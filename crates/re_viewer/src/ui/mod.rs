@@ -2,6 +2,7 @@ mod annotations;
 mod auto_layout;
 mod blueprint;
 mod data_blueprint;
+mod plot_following;
 mod scene;
 mod selection_history;
 mod selection_history_ui;
@@ -16,6 +17,7 @@ mod view_text;
 mod view_time_series;
 mod viewport;
 
+pub(crate) mod bandwidth_panel;
 pub(crate) mod data_ui;
 pub(crate) mod memory_panel;
 pub(crate) mod selection_panel;
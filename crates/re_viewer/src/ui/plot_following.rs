@@ -0,0 +1,64 @@
+use egui::plot::Plot;
+use egui::util::History;
+
+/// Lets the user pause a live plot to inspect a past spike, while its [`History`] keeps accumulating.
+///
+/// While paused, the plot shows a snapshot of the history taken at the time of pausing,
+/// so the data doesn't age out from under the user.
+pub struct PlotFollowing<T> {
+    /// `None` while following the live data.
+    snapshot: Option<Vec<(f64, T)>>,
+}
+
+impl<T> Default for PlotFollowing<T> {
+    fn default() -> Self {
+        Self { snapshot: None }
+    }
+}
+
+impl<T: Copy> PlotFollowing<T> {
+    pub fn is_following(&self) -> bool {
+        self.snapshot.is_none()
+    }
+
+    /// The pause/follow toggle button.
+    pub fn ui(&mut self, ui: &mut egui::Ui, history: &History<T>) {
+        if self.is_following() {
+            if ui
+                .small_button("⏸ Pause")
+                .on_hover_text("Freeze the plot to inspect it, data keeps being recorded")
+                .clicked()
+            {
+                self.snapshot = Some(history.iter().collect());
+            }
+        } else if ui
+            .small_button("▶ Follow")
+            .on_hover_text("Snap back to the live data")
+            .clicked()
+        {
+            self.snapshot = None;
+        }
+    }
+
+    /// The points to plot, either live or as they were when the plot was paused.
+    pub fn points(&self, history: &History<T>) -> Vec<(f64, T)> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.clone(),
+            None => history.iter().collect(),
+        }
+    }
+
+    /// Keeps the plot scrolling along while following, and lets the user navigate it while paused.
+    pub fn plot(&self, plot: Plot) -> Plot {
+        let interactive = !self.is_following();
+        let plot = plot
+            .allow_drag(interactive)
+            .allow_zoom(interactive)
+            .allow_scroll(interactive);
+        if interactive {
+            plot
+        } else {
+            plot.reset()
+        }
+    }
+}
@@ -6,8 +6,8 @@ use itertools::Itertools;
 use poll_promise::Promise;
 use re_arrow_store::{LatestAtQuery, RangeQuery, TimeInt, TimeRange, Timeline};
 use re_data_store::{
-    query_latest_single, Colormap, ColorMapper, EditableAutoValue, EntityPath, EntityProperties,
-    ExtraQueryHistory,
+    query_latest_single, BackprojectRadiusMode, Colormap, ColorMapper, DepthUnit,
+    EditableAutoValue, EntityPath, EntityProperties, ExtraQueryHistory,
 };
 use re_log_types::{
     component_types::{ImuData, InstanceKey, Tensor, TensorDataMeaning},
@@ -24,12 +24,20 @@ use crate::{
 
 use egui_dock::{DockArea, NodeIndex, Tree};
 
-use super::{data_ui::DataUi, space_view::ViewState, SpaceView, ViewCategory};
+use super::{
+    data_blueprint::DataBlueprintTree, data_ui::DataUi, space_view::ViewState, SpaceView,
+    ViewCategory,
+};
 
 use egui::emath::History;
 
+use super::plot_following::PlotFollowing;
+
 // ---
 
+const DEPTH_PRESET_DOCS_URL: &str =
+    "https://docs.luxonis.com/projects/api/en/latest/components/nodes/stereo_depth/";
+
 #[derive(Debug, Copy, Clone)]
 enum XYZ {
     X,
@@ -44,9 +52,120 @@ enum ImuTabKind {
     Mag,
 }
 
+/// How the device configuration is laid out in the selection panel.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+enum ConfigLayout {
+    /// Every section in one vertical list of collapsing headers.
+    #[default]
+    List,
+
+    /// One tab per group of settings, takes less vertical space.
+    Tabs,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ConfigTab {
+    Cameras,
+    Depth,
+    Ai,
+    Imu,
+    Stats,
+}
+
+impl ConfigTab {
+    fn tree() -> Tree<ConfigTab> {
+        Tree::new(vec![
+            ConfigTab::Cameras,
+            ConfigTab::Depth,
+            ConfigTab::Ai,
+            ConfigTab::Imu,
+            ConfigTab::Stats,
+        ])
+    }
+}
+
+impl From<depthai::ConfigSection> for ConfigTab {
+    fn from(section: depthai::ConfigSection) -> Self {
+        match section {
+            depthai::ConfigSection::ColorCamera
+            | depthai::ConfigSection::LeftCamera
+            | depthai::ConfigSection::RightCamera => ConfigTab::Cameras,
+            depthai::ConfigSection::Depth => ConfigTab::Depth,
+            depthai::ConfigSection::AiModel => ConfigTab::Ai,
+        }
+    }
+}
+
+/// Whether `tab` is the active tab of any node, i.e. its ui is drawn this frame.
+fn is_tab_shown(tree: &Tree<ConfigTab>, tab: ConfigTab) -> bool {
+    tree.iter().any(|node| {
+        let egui_dock::Node::Leaf { tabs, active, .. } = node else {
+            return false;
+        };
+        tabs.get(active.0) == Some(&tab)
+    })
+}
+
+struct ConfigTabViewer<'t, 'a, 'b> {
+    tabs: &'t mut DepthaiTabs<'a, 'b>,
+    device_config: &'t mut depthai::DeviceConfig,
+    focused_section: Option<depthai::ConfigSection>,
+    changed: bool, // Whether any tab changed `device_config` this frame
+}
+
+impl<'t, 'a, 'b> egui_dock::TabViewer for ConfigTabViewer<'t, 'a, 'b> {
+    type Tab = ConfigTab;
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        egui::ScrollArea::vertical().show(ui, |ui| match tab {
+            ConfigTab::Cameras => {
+                self.changed |=
+                    self.tabs
+                        .cameras_config_ui(ui, self.device_config, self.focused_section);
+            }
+            ConfigTab::Depth => {
+                self.changed |=
+                    self.tabs
+                        .depth_config_ui(ui, self.device_config, self.focused_section);
+            }
+            ConfigTab::Ai => {
+                self.changed |=
+                    self.tabs
+                        .ai_config_ui(ui, self.device_config, self.focused_section);
+            }
+            ConfigTab::Imu => self.tabs.imu_stats_ui(ui),
+            ConfigTab::Stats => {
+                if let Some(stats) = &self.tabs.ctx.depthai_state.device_stats {
+                    device_stats_ui(ui, stats);
+                } else {
+                    ui.weak("No stats from the device yet");
+                }
+                let depthai_state = &self.tabs.ctx.depthai_state;
+                frame_sync_ui(
+                    ui,
+                    &depthai_state.frame_sync,
+                    depthai_state.device_config.config.slowest_camera_fps(),
+                );
+            }
+        });
+    }
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        match tab {
+            ConfigTab::Cameras => "Cameras",
+            ConfigTab::Depth => "Depth",
+            ConfigTab::Ai => "AI",
+            ConfigTab::Imu => "IMU",
+            ConfigTab::Stats => "Stats",
+        }
+        .into()
+    }
+}
+
 struct ImuXyzTabs<'a> {
     kind: ImuTabKind,
     data: &'a mut History<[f32; 3]>,
+    following: &'a PlotFollowing<[f32; 3]>,
 }
 
 impl<'a> ImuXyzTabs<'a> {
@@ -67,14 +186,13 @@ impl<'a> egui_dock::TabViewer for ImuXyzTabs<'a> {
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
         ui.add_sized([ui.available_width(), 150.0], |ui: &mut egui::Ui| {
-            Plot::new(format!("{:?} ({tab:?})", self.kind))
-                .allow_drag(false)
-                .allow_zoom(false)
-                .allow_scroll(false)
+            self.following
+                .plot(Plot::new(format!("{:?} ({tab:?})", self.kind)))
                 .show(ui, |plot_ui| {
                     plot_ui.line(Line::new(PlotPoints::new(
-                        self.data
-                            .iter()
+                        self.following
+                            .points(self.data)
+                            .into_iter()
                             .map(|(t, v)| [t, v[*tab as usize].into()])
                             .collect_vec(),
                     )))
@@ -89,8 +207,13 @@ struct DepthaiTabs<'a, 'b> {
     accel_history: &'a mut History<[f32; 3]>,
     gyro_history: &'a mut History<[f32; 3]>,
     magnetometer_history: &'a mut History<[f32; 3]>,
+    accel_following: &'a mut PlotFollowing<[f32; 3]>,
+    gyro_following: &'a mut PlotFollowing<[f32; 3]>,
+    magnetometer_following: &'a mut PlotFollowing<[f32; 3]>,
     now: f64, // Time elapsed from spawning SelectionPanel
     imu_accel_tabs: &'a mut Tree<XYZ>,
+    config_layout: &'a mut ConfigLayout,
+    config_tabs: &'a mut Tree<ConfigTab>,
 }
 
 impl<'a, 'b> DepthaiTabs<'a, 'b> {
@@ -101,170 +224,1555 @@ impl<'a, 'b> DepthaiTabs<'a, 'b> {
         tree
     }
 
+    /// Shown for as long as the device runs hot, with a config that lowers the load.
+    fn temperature_warning_ui(&mut self, ui: &mut egui::Ui) {
+        let state = &mut *self.ctx.depthai_state;
+        if !state.temperature_history.is_overheating() {
+            return;
+        }
+        let safe_config = state.device_config.config.reduced_load();
+        let can_apply = !state.playback && safe_config != state.device_config.config;
+        let mut apply = false;
+        egui::Frame::none()
+            .fill(ui.visuals().faint_bg_color)
+            .inner_margin(egui::Margin::same(4.0))
+            .show(ui, |ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!(
+                        "🌡 The device averaged {:.0} °C over the last {:.0} s (peak {:.0} °C), \
+                        it may be thermal throttling.",
+                        state.temperature_history.average_c().unwrap_or_default(),
+                        depthai::TemperatureHistory::WINDOW_SECS,
+                        state.temperature_history.max_c().unwrap_or_default(),
+                    ),
+                );
+                ui.label("Lower the FPS or resolution, or improve the airflow around the device.");
+                apply = ui
+                    .add_enabled(can_apply, egui::Button::new("Apply safe config"))
+                    .on_hover_text(
+                        "Halve the frame rates, use the lowest color resolution \
+                        and turn off the depth filters",
+                    )
+                    .on_disabled_hover_text("There is nothing left to lower")
+                    .clicked();
+            });
+        if apply {
+            let mut config = safe_config;
+            state.set_device_config(&mut config);
+        }
+    }
+
+    fn error_banner_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(error) = &self.ctx.depthai_state.last_error else {
+            return;
+        };
+        let mut dismissed = false;
+        let mut force_release = false;
+        egui::Frame::none()
+            .fill(ui.visuals().faint_bg_color)
+            .inner_margin(egui::Margin::same(4.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        if let Some(explanation) = error.kind.explanation() {
+                            ui.colored_label(ui.visuals().error_fg_color, explanation)
+                                .on_hover_text(&error.message);
+                        } else {
+                            ui.colored_label(ui.visuals().error_fg_color, &error.message);
+                        }
+                        if error.action == depthai::ErrorAction::FullReset {
+                            ui.label("The device was reset, select it again to continue.");
+                        }
+                        if error.kind == depthai::ErrorKind::DeviceBusy
+                            && ui
+                                .button("Force release & retry")
+                                .on_hover_text(
+                                    "Close every handle to the device and start the pipeline again",
+                                )
+                                .clicked()
+                        {
+                            force_release = true;
+                        }
+                    });
+                    if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+        if dismissed {
+            self.ctx.depthai_state.last_error = None;
+        }
+        if force_release {
+            self.ctx.depthai_state.force_release_and_retry();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn presets_ui(&mut self, ui: &mut egui::Ui) {
+        let mut load_preset = None;
+        let editable =
+            self.ctx.depthai_state.selected_device.id != "" && !self.ctx.depthai_state.playback;
+        ui.add_enabled_ui(editable, |ui| {
+            ui.horizontal(|ui| {
+                let presets_state = &mut self.ctx.depthai_state.device_config;
+                ui.label("Preset: ");
+                let popup_id = ui.make_persistent_id("device_config_preset").with("popup");
+                let was_open = ui.memory(|memory| memory.is_popup_open(popup_id));
+                egui::ComboBox::from_id_source("device_config_preset")
+                    .width(combo_width(
+                        ui,
+                        presets_state
+                            .presets
+                            .iter()
+                            .map(String::as_str)
+                            .chain(["None"]),
+                    ))
+                    .selected_text(if presets_state.selected_preset.is_empty() {
+                        "None"
+                    } else {
+                        presets_state.selected_preset.as_str()
+                    })
+                    .show_ui(ui, |ui| {
+                        // Presets may have been added outside the viewer, re-read them once when opened.
+                        if !was_open {
+                            presets_state.refresh_presets();
+                        }
+                        for preset in &presets_state.presets {
+                            if ui
+                                .selectable_label(presets_state.selected_preset == *preset, preset)
+                                .clicked()
+                            {
+                                load_preset = Some(preset.clone());
+                            }
+                        }
+                    });
+                ui.menu_button("Save as…", |ui| {
+                    ui.text_edit_singleline(&mut presets_state.new_preset_name);
+                    if ui
+                        .add_enabled(
+                            !presets_state.new_preset_name.trim().is_empty(),
+                            egui::Button::new("Save"),
+                        )
+                        .clicked()
+                    {
+                        let name = std::mem::take(&mut presets_state.new_preset_name);
+                        presets_state.save_preset(name.trim());
+                        ui.close_menu();
+                    }
+                });
+                if ui
+                    .add_enabled(
+                        !presets_state.selected_preset.is_empty(),
+                        egui::Button::new("Delete"),
+                    )
+                    .clicked()
+                {
+                    let name = presets_state.selected_preset.clone();
+                    presets_state.delete_preset(&name);
+                }
+            });
+        });
+        if let Some(name) = load_preset {
+            self.ctx.depthai_state.load_preset(&name);
+        }
+    }
+
+    /// Lets the user stop streaming channels they don't need, without touching the pipeline.
+    fn streams_ui(&mut self, ui: &mut egui::Ui) {
+        let paused = self.ctx.depthai_state.is_streaming_paused();
+        ui.add_enabled_ui(!paused, |ui| {
+            ui.collapsing("Streams", |ui| {
+                ui.checkbox(
+                    &mut self.ctx.depthai_state.only_subscribe_to_visible,
+                    "Only stream visible",
+                )
+                .on_hover_text(
+                    "Only stream what is shown in a space view to save bandwidth, \
+                otherwise stream everything that is configured",
+                );
+                for (channel, label, command) in [
+                    (
+                        depthai::ChannelId::ColorImage,
+                        "Show Color",
+                        re_ui::Command::ToggleColorStream,
+                    ),
+                    (
+                        depthai::ChannelId::LeftMono,
+                        "Show Left Mono",
+                        re_ui::Command::ToggleLeftMonoStream,
+                    ),
+                    (
+                        depthai::ChannelId::RightMono,
+                        "Show Right Mono",
+                        re_ui::Command::ToggleRightMonoStream,
+                    ),
+                    (
+                        depthai::ChannelId::DepthImage,
+                        "Show Depth",
+                        re_ui::Command::ToggleDepthStream,
+                    ),
+                    (
+                        depthai::ChannelId::PointCloud,
+                        "Show Point Cloud",
+                        re_ui::Command::TogglePointCloudStream,
+                    ),
+                ] {
+                    let mut subscribed = self.ctx.depthai_state.subscriptions.contains(&channel);
+                    let mut response = ui.checkbox(&mut subscribed, label);
+                    if let Some(shortcut) = command.kb_shortcut() {
+                        response = response.on_hover_text(format!(
+                            "Toggle with {}",
+                            ui.ctx().format_shortcut(&shortcut)
+                        ));
+                    }
+                    if response.changed() {
+                        self.ctx.depthai_state.toggle_subscription(channel);
+                    }
+                }
+                // Subscribed to with the rest once the views are synced.
+                ui.checkbox(
+                    &mut self.ctx.depthai_state.show_color_preview,
+                    "Show preview",
+                )
+                .on_hover_text(
+                    "A low resolution, low FPS color stream for framing the scene, \
+                    much cheaper than the full color stream",
+                );
+                let depth_enabled = self.ctx.depthai_state.device_config.config.depth.is_some();
+                ui.add_enabled_ui(depth_enabled, |ui| {
+                    for (channel, label) in [
+                        (depthai::ChannelId::LeftRectified, "Show rectified left"),
+                        (depthai::ChannelId::RightRectified, "Show rectified right"),
+                    ] {
+                        let mut subscribed =
+                            self.ctx.depthai_state.subscriptions.contains(&channel);
+                        if ui
+                            .checkbox(&mut subscribed, label)
+                            .on_hover_text(
+                                "The mono image as the stereo matching sees it, \
+                                to check the calibration",
+                            )
+                            .on_disabled_hover_text("Only streamed while depth is enabled")
+                            .changed()
+                        {
+                            self.ctx.depthai_state.toggle_subscription(channel);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let fps_cap = &mut self.ctx.depthai_state.host_fps_cap;
+                    let mut capped = fps_cap.is_some();
+                    if ui
+                        .checkbox(&mut capped, "Cap logged FPS:")
+                        .on_hover_text(
+                            "Drop frames in the viewer to save memory and disk, \
+                            the cameras keep running at their own FPS",
+                        )
+                        .changed()
+                    {
+                        *fps_cap = capped.then_some(5);
+                    }
+                    if let Some(fps) = fps_cap {
+                        numeric_entry_ui(ui, fps, depthai::MIN_FPS..=60, " FPS");
+                    }
+                });
+            });
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn recording_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if let Some(recording) = &self.ctx.depthai_state.recording {
+                let stop = ui
+                    .button("⏹ Stop recording")
+                    .on_hover_text(format!("Recording to {:?}", recording.path))
+                    .clicked();
+                let secs = recording.duration().as_secs();
+                ui.label(format!(
+                    "{:02}:{:02} · {}",
+                    secs / 60,
+                    secs % 60,
+                    re_format::format_bytes(recording.size_bytes() as _)
+                ));
+                if stop {
+                    self.ctx.depthai_state.stop_recording();
+                }
+            } else if ui
+                .add_enabled(
+                    !self.ctx.depthai_state.playback,
+                    egui::Button::new("⏺ Record"),
+                )
+                .on_hover_text("Write the incoming streams to an .rrd file for later review")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name("depthai.rrd")
+                    .add_filter("rerun data file", &["rrd"])
+                    .save_file()
+                {
+                    let begin_msg = self
+                        .ctx
+                        .log_db
+                        .recording_msg()
+                        .map(|msg| re_log_types::LogMsg::BeginRecordingMsg(msg.clone()));
+                    self.ctx.depthai_state.start_recording(path, begin_msg);
+                }
+            }
+            if ui
+                .button("Load recording…")
+                .on_hover_text("Review a recorded session, no device needed")
+                .clicked()
+            {
+                self.ctx.depthai_state.load_recording_requested = true;
+            }
+        });
+    }
+
+    fn calibration_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let state = &mut self.ctx.depthai_state;
+            match &state.calibration_source {
+                depthai::CalibrationSource::Device => {
+                    ui.label("Calibration: device");
+                }
+                depthai::CalibrationSource::File(name) => {
+                    ui.label(format!("Calibration: {name}"))
+                        .on_hover_text("Overrides the device calibration until the device is changed");
+                    if ui.small_button("Reset").clicked() {
+                        state.reset_calibration();
+                    }
+                }
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("Load calibration…")
+                .on_hover_text("Use a DepthAI calibration json instead of the calibration stored on the device")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("DepthAI calibration", &["json"])
+                    .pick_file()
+                {
+                    state.load_calibration(&path);
+                }
+            }
+        });
+    }
+
+    fn display_names_ui(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Display names", |ui| {
+            egui::Grid::new("channel_display_names")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for channel in [
+                        depthai::ChannelId::ColorImage,
+                        depthai::ChannelId::ColorPreview,
+                        depthai::ChannelId::LeftMono,
+                        depthai::ChannelId::RightMono,
+                        depthai::ChannelId::LeftRectified,
+                        depthai::ChannelId::RightRectified,
+                        depthai::ChannelId::DepthImage,
+                        depthai::ChannelId::PointCloud,
+                        depthai::ChannelId::Disparity,
+                        depthai::ChannelId::DepthConfidence,
+                        depthai::ChannelId::Detections,
+                        depthai::ChannelId::SpatialDetections,
+                        depthai::ChannelId::ImuData,
+                    ] {
+                        let names = &mut self.ctx.depthai_state.channel_display_names;
+                        let mut name = names.get(&channel).cloned().unwrap_or_default();
+                        ui.label(format!("{channel:?}"));
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut name)
+                                    .hint_text("Entity path")
+                                    .desired_width(120.0),
+                            )
+                            .changed()
+                        {
+                            if name.is_empty() {
+                                names.remove(&channel);
+                            } else {
+                                names.insert(channel, name);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
     fn device_configuration_ui(&mut self, ui: &mut egui::Ui) {
         // re_log::info!("pipeline_state: {:?}", pipeline_state);
-        let mut device_config = self.ctx.depthai_state.device_config.config.clone();
-        let mut depth_enabled = device_config.depth.is_some();
-        let mut depth = device_config.depth.unwrap_or_default();
+        if let Some(hello) = &self.ctx.depthai_state.backend_hello {
+            if !hello.is_compatible() {
+                // Nothing below is going to work with a backend that speaks a different api.
+                egui::Frame::none()
+                    .fill(ui.visuals().faint_bg_color)
+                    .inner_margin(egui::Margin::same(4.0))
+                    .show(ui, |ui| {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            format!(
+                                "The backend ({}) is incompatible with this viewer ({}). \
+                                Update both to the same release to continue.",
+                                hello.backend_version,
+                                env!("CARGO_PKG_VERSION")
+                            ),
+                        );
+                    });
+                return;
+            }
+        }
+        self.error_banner_ui(ui);
+        self.temperature_warning_ui(ui);
+        if self.ctx.depthai_state.playback {
+            egui::Frame::none()
+                .fill(ui.visuals().faint_bg_color)
+                .inner_margin(egui::Margin::same(4.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.strong("▶ Playback");
+                        ui.label("Reviewing a recording, the device configuration is read-only.");
+                    });
+                });
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.presets_ui(ui);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.recording_ui(ui);
+        self.config_layout_ui(ui);
+        self.config_history_ui(ui);
+        self.staged_config_ui(ui);
+        let mut device_config = self
+            .ctx
+            .depthai_state
+            .device_config
+            .staged
+            .clone()
+            .unwrap_or_else(|| self.ctx.depthai_state.device_config.config.clone());
+        let mut update_device_config = false;
+        let focused_section = self.ctx.depthai_state.focused_config_section.take();
+        let editable =
+            self.ctx.depthai_state.selected_device.id != "" && !self.ctx.depthai_state.playback;
+        ui.add_enabled_ui(editable, |ui| {
+            ui.vertical(|ui| {
+                self.streams_ui(ui);
+                self.display_names_ui(ui);
+                self.calibration_ui(ui);
+                match *self.config_layout {
+                    ConfigLayout::List => {
+                        update_device_config |=
+                            self.cameras_config_ui(ui, &mut device_config, focused_section);
+                        update_device_config |=
+                            self.depth_config_ui(ui, &mut device_config, focused_section);
+                        update_device_config |=
+                            self.ai_config_ui(ui, &mut device_config, focused_section);
+                    }
+                    ConfigLayout::Tabs => {
+                        update_device_config |=
+                            self.config_tabs_ui(ui, &mut device_config, focused_section);
+                    }
+                }
+            });
+            if update_device_config {
+                self.ctx.depthai_state.change_device_config(device_config);
+            }
+        });
+        self.raw_pipeline_ui(ui);
+    }
+
+    /// The exact JSON exchanged with the backend, for debugging without rebuilding with logging.
+    fn raw_pipeline_ui(&mut self, ui: &mut egui::Ui) {
+        let backend_comms = &self.ctx.depthai_state.backend_comms;
+        egui::CollapsingHeader::new("Raw pipeline")
+            .default_open(false)
+            .show(ui, |ui| {
+                if let Some(json) = &backend_comms.sent_pipeline_json {
+                    raw_json_text_ui(ui, "Last sent to the backend", json);
+                } else {
+                    ui.weak("No pipeline has been sent yet");
+                }
+                if let Some(json) = &backend_comms.received_pipeline_json {
+                    raw_json_text_ui(ui, "Last reply from the backend", json);
+                } else {
+                    ui.weak("The backend hasn't replied to a pipeline yet");
+                }
+            });
+    }
+
+    /// Hold changes back to validate them before the pipeline restarts, for setups that can't afford a dropped stream.
+    fn staged_config_ui(&mut self, ui: &mut egui::Ui) {
+        let state = &mut *self.ctx.depthai_state;
+        let editable = state.selected_device.id != "" && !state.playback;
+        let mut validate = false;
+        let mut apply = false;
+        ui.add_enabled_ui(editable, |ui| {
+            let config_state = &mut state.device_config;
+            if ui
+                .checkbox(
+                    &mut config_state.validate_before_apply,
+                    "Validate before applying",
+                )
+                .on_hover_text(
+                    "Hold changes back until they're applied, \
+                    so they can be checked without restarting the running pipeline",
+                )
+                .changed()
+                && !config_state.validate_before_apply
+            {
+                // Nothing would apply the held back changes anymore.
+                apply = true;
+            }
+            if config_state.staged.is_none() {
+                return;
+            }
+            egui::Frame::none()
+                .fill(ui.visuals().faint_bg_color)
+                .inner_margin(egui::Margin::same(4.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Unapplied changes");
+                        validate = ui
+                            .add_enabled(
+                                !config_state.validation_in_progress,
+                                egui::Button::new("Validate"),
+                            )
+                            .on_hover_text("Check with the backend that the pipeline would start")
+                            .clicked();
+                        apply = ui
+                            .button("Apply")
+                            .on_hover_text("Restart the pipeline with the changes")
+                            .clicked();
+                        if ui.button("Discard").clicked() {
+                            config_state.staged = None;
+                            config_state.validation = None;
+                            config_state.validation_in_progress = false;
+                        }
+                    });
+                    if config_state.validation_in_progress {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Validating…");
+                        });
+                    }
+                    if let Some(validation) = &config_state.validation {
+                        if validation.is_ok() && validation.warnings.is_empty() {
+                            ui.label("✔ The pipeline would start");
+                        }
+                        for error in &validation.errors {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
+                        for warning in &validation.warnings {
+                            ui.colored_label(ui.visuals().warn_fg_color, warning);
+                        }
+                    }
+                });
+        });
+        if validate {
+            state.validate_staged_config();
+        }
+        if apply {
+            state.apply_staged_config();
+        }
+    }
+
+    /// Undo and redo applied configs, e.g. to get back to one that streamed fine.
+    fn config_history_ui(&mut self, ui: &mut egui::Ui) {
+        let editable =
+            self.ctx.depthai_state.selected_device.id != "" && !self.ctx.depthai_state.playback;
+        let mut undo = false;
+        let mut redo = false;
+        ui.add_enabled_ui(editable, |ui| {
+            ui.horizontal(|ui| {
+                let history = &self.ctx.depthai_state.device_config.history;
+                undo = ui
+                    .add_enabled(history.can_undo(), egui::Button::new("⟲ Undo"))
+                    .on_hover_text("Go back to the previously applied config")
+                    .clicked();
+                redo = ui
+                    .add_enabled(history.can_redo(), egui::Button::new("⟳ Redo"))
+                    .on_hover_text("Apply the config that was undone again")
+                    .clicked();
+                ui.menu_button("History", |ui| {
+                    let mut previous: Option<&depthai::DeviceConfig> = None;
+                    for (i, (entry, current)) in history.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            let text = format!("#{}", i + 1);
+                            if current {
+                                ui.strong(text);
+                            } else {
+                                ui.label(text);
+                            }
+                            if entry.started {
+                                ui.label("✔ Started");
+                            } else {
+                                ui.weak("Not started");
+                            }
+                        })
+                        .response
+                        .on_hover_text(match previous {
+                            Some(previous) => {
+                                let changes = entry.config.changes_from(previous);
+                                if changes.is_empty() {
+                                    "Same as the config before".to_owned()
+                                } else {
+                                    changes.join("\n")
+                                }
+                            }
+                            None => "The oldest config in the history".to_owned(),
+                        });
+                        previous = Some(&entry.config);
+                    }
+                });
+            });
+        });
+        if undo {
+            self.ctx.depthai_state.undo_device_config();
+        }
+        if redo {
+            self.ctx.depthai_state.redo_device_config();
+        }
+    }
+
+    fn config_layout_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Layout: ");
+            ui.selectable_value(self.config_layout, ConfigLayout::List, "List")
+                .on_hover_text("All settings in one collapsible list");
+            ui.selectable_value(self.config_layout, ConfigLayout::Tabs, "Tabs")
+                .on_hover_text("One tab per group of settings, for smaller screens");
+        });
+    }
+
+    /// Shows the settings in dockable tabs, returns true if `device_config` changed.
+    fn config_tabs_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device_config: &mut depthai::DeviceConfig,
+        focused_section: Option<depthai::ConfigSection>,
+    ) -> bool {
+        if !is_tab_shown(self.config_tabs, ConfigTab::Imu) {
+            self.unsubscribe_hidden_imu();
+        }
+        // Taken out for the duration of the frame so the tab viewer can borrow the rest of `self`
+        let mut tree = std::mem::replace(self.config_tabs, Tree::new(Vec::new()));
+        if let Some(section) = focused_section {
+            if let Some((node_index, tab_index)) = tree.find_tab(&ConfigTab::from(section)) {
+                tree.set_focused_node(node_index);
+                tree.set_active_tab(node_index, tab_index);
+            }
+        }
+        let mut viewer = ConfigTabViewer {
+            tabs: self,
+            device_config,
+            focused_section,
+            changed: false,
+        };
+        ui.add_sized([ui.available_width(), 400.0], |ui: &mut egui::Ui| {
+            ui.vertical(|ui| {
+                DockArea::new(&mut tree)
+                    .id(egui::Id::new("depthai_config_tabs"))
+                    .style(re_ui::egui_dock_style(ui.style()))
+                    .show_inside(ui, &mut viewer);
+            })
+            .response
+        });
+        let changed = viewer.changed;
+        *self.config_tabs = tree;
+        changed
+    }
+
+    /// Returns true if `device_config` changed.
+    fn cameras_config_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device_config: &mut depthai::DeviceConfig,
+        focused_section: Option<depthai::ConfigSection>,
+    ) -> bool {
         let mut update_device_config = false;
-        ui.add_enabled_ui(self.ctx.depthai_state.selected_device.id != "", |ui| {
+        let selected_device = self.ctx.depthai_state.selected_device.clone();
+        let readback = self.ctx.depthai_state.exposure_readback.unwrap_or_default();
+        config_section_header(
+            "Color Camera",
+            depthai::ConfigSection::ColorCamera,
+            focused_section,
+        )
+        .show(ui, |ui| {
             ui.vertical(|ui| {
-                ui.collapsing("Color Camera", |ui| {
+                if ui
+                    .checkbox(&mut device_config.color_camera.enabled, "Enabled")
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Resolution: ");
+                    egui::ComboBox::from_id_source("color_camera_resolution")
+                        .width(combo_width(ui, depthai::ColorCameraResolution::ALL))
+                        .selected_text(format!("{}", device_config.color_camera.resolution))
+                        .show_ui(ui, |ui| {
+                            for resolution in depthai::ColorCameraResolution::ALL {
+                                if !selected_device.supports_color_resolution(resolution) {
+                                    continue;
+                                }
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.color_camera.resolution,
+                                        resolution,
+                                        resolution.to_string(),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Socket: ");
+                    egui::ComboBox::from_id_source("color_camera_socket")
+                        .width(combo_width(
+                            ui,
+                            depthai::BoardSocket::COLOR.map(|socket| format!("{socket:?}")),
+                        ))
+                        .selected_text(format!("{:?}", device_config.color_camera.board_socket))
+                        .show_ui(ui, |ui| {
+                            for socket in depthai::BoardSocket::COLOR {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.color_camera.board_socket,
+                                        socket,
+                                        format!("{socket:?}"),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                let max_fps = device_config.color_camera.resolution.max_fps();
+                if fps_ui(ui, &mut device_config.color_camera.fps, max_fps) {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Orientation: ");
+                    egui::ComboBox::from_id_source("color_camera_orientation")
+                        .width(combo_width(ui, depthai::CameraImageOrientation::ALL))
+                        .selected_text(format!("{}", device_config.color_camera.orientation))
+                        .show_ui(ui, |ui| {
+                            for orientation in depthai::CameraImageOrientation::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.color_camera.orientation,
+                                        orientation,
+                                        format!("{orientation}"),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Encoding: ");
+                    egui::ComboBox::from_id_source("color_camera_encoding")
+                        .width(combo_width(ui, depthai::ColorEncoding::ALL))
+                        .selected_text(device_config.color_camera.encoding.to_string())
+                        .show_ui(ui, |ui| {
+                            for encoding in depthai::ColorEncoding::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.color_camera.encoding,
+                                        encoding,
+                                        encoding.to_string(),
+                                    )
+                                    .on_hover_text(encoding.description())
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(device_config.color_camera.encoding.description());
+                });
+                if ui
+                    .checkbox(
+                        &mut device_config.color_camera.auto_exposure,
+                        "Auto exposure",
+                    )
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.add_enabled_ui(!device_config.color_camera.auto_exposure, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Exposure: ");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut device_config.color_camera.exposure_us)
+                                    .clamp_range(depthai::COLOR_CAMERA_EXPOSURE_RANGE_US)
+                                    .suffix(" µs"),
+                            )
+                            .changed()
+                        {
+                            update_device_config = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("ISO: ");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut device_config.color_camera.iso)
+                                    .clamp_range(depthai::COLOR_CAMERA_ISO_RANGE),
+                            )
+                            .changed()
+                        {
+                            update_device_config = true;
+                        }
+                    });
+                });
+                if ui
+                    .checkbox(
+                        &mut device_config.color_camera.auto_white_balance,
+                        "Auto white balance",
+                    )
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.add_enabled_ui(!device_config.color_camera.auto_white_balance, |ui| {
+                    if ui
+                        .add(
+                            egui::Slider::new(
+                                &mut device_config.color_camera.white_balance_k,
+                                depthai::COLOR_CAMERA_WHITE_BALANCE_RANGE_K,
+                            )
+                            .suffix(" K")
+                            .text("White balance"),
+                        )
+                        .changed()
+                    {
+                        update_device_config = true;
+                    }
+                });
+                if ui
+                    .checkbox(&mut device_config.color_camera.auto_focus, "Auto focus")
+                    .on_hover_text(
+                        "Turn off to lock the focus, e.g. when inspecting objects at a fixed \
+                        distance. Only modules with a motorized lens can focus.",
+                    )
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.add_enabled_ui(!device_config.color_camera.auto_focus, |ui| {
+                    if ui
+                        .add(
+                            egui::Slider::new(
+                                &mut device_config.color_camera.focus_position,
+                                0..=255,
+                            )
+                            .text("Lens position"),
+                        )
+                        .on_hover_text("0 focuses far away, 255 focuses closest")
+                        .changed()
+                    {
+                        update_device_config = true;
+                    }
+                });
+                exposure_readback_ui(ui, readback.color);
+                ui.horizontal(|ui| {
+                    ui.label("ISP scale: ");
+                    let isp_scale = &mut device_config.color_camera.isp_scale;
+                    let numerator_changed = ui
+                        .add(egui::DragValue::new(&mut isp_scale.0).clamp_range(1..=16))
+                        .changed();
+                    ui.label("/");
+                    let denominator_changed = ui
+                        .add(egui::DragValue::new(&mut isp_scale.1).clamp_range(1..=16))
+                        .changed();
+                    if numerator_changed || denominator_changed {
+                        update_device_config = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Crop: ");
+                    if let Some((x_min, y_min, x_max, y_max)) = device_config.color_camera.crop_roi
+                    {
+                        ui.label(format!(
+                            "{:.0}% × {:.0}%",
+                            (x_max - x_min) * 100.0,
+                            (y_max - y_min) * 100.0
+                        ))
+                        .on_hover_text(format!(
+                            "From ({x_min:.2}, {y_min:.2}) to ({x_max:.2}, {y_max:.2}) of the full frame"
+                        ));
+                    } else {
+                        ui.weak("Full frame");
+                    }
+                    ui.toggle_value(&mut self.ctx.depthai_state.drawing_crop, "✏ Draw")
+                        .on_hover_text(
+                            "Drag a rectangle on the color camera view to only stream that region \
+                            at full detail",
+                        );
+                    if ui
+                        .add_enabled(
+                            device_config.color_camera.crop_roi.is_some(),
+                            egui::Button::new("Clear"),
+                        )
+                        .on_hover_text("Stream the full frame again")
+                        .clicked()
+                    {
+                        device_config.color_camera.crop_roi = None;
+                        update_device_config = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if self.ctx.depthai_state.capture_in_progress {
+                        ui.spinner();
+                    } else if ui
+                        .button("📷 Capture still")
+                        .on_hover_text("Capture a single full resolution frame")
+                        .clicked()
+                    {
+                        self.ctx.depthai_state.capture_still();
+                    }
+                });
+            });
+        });
+        if !selected_device.has_stereo() {
+            return update_device_config;
+        }
+        config_section_header(
+            "Left Mono Camera",
+            depthai::ConfigSection::LeftCamera,
+            focused_section,
+        )
+        .show(ui, |ui| {
+            ui.vertical(|ui| {
+                if ui
+                    .checkbox(&mut device_config.left_camera.enabled, "Enabled")
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Resolution: ");
+                    egui::ComboBox::from_id_source("left_camera_resolution")
+                        .width(combo_width(ui, depthai::MonoCameraResolution::ALL))
+                        .selected_text(format!("{}", device_config.left_camera.resolution))
+                        .show_ui(ui, |ui| {
+                            for resolution in depthai::MonoCameraResolution::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.left_camera.resolution,
+                                        resolution,
+                                        resolution.to_string(),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                let max_fps = device_config.left_camera.resolution.max_fps();
+                if fps_ui(ui, &mut device_config.left_camera.fps, max_fps) {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Socket: ");
+                    egui::ComboBox::from_id_source("left_camera_socket")
+                        .width(combo_width(
+                            ui,
+                            depthai::BoardSocket::MONO.map(|socket| format!("{socket:?}")),
+                        ))
+                        .selected_text(format!("{:?}", device_config.left_camera.board_socket))
+                        .show_ui(ui, |ui| {
+                            for socket in depthai::BoardSocket::MONO {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.left_camera.board_socket,
+                                        socket,
+                                        format!("{socket:?}"),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Orientation: ");
+                    egui::ComboBox::from_id_source("left_camera_orientation")
+                        .width(combo_width(ui, depthai::CameraImageOrientation::ALL))
+                        .selected_text(format!("{}", device_config.left_camera.orientation))
+                        .show_ui(ui, |ui| {
+                            for orientation in depthai::CameraImageOrientation::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.left_camera.orientation,
+                                        orientation,
+                                        format!("{orientation}"),
+                                    )
+                                    .changed()
+                                {
+                                    // Depth needs both mono cameras in the same orientation
+                                    device_config.right_camera.orientation = orientation;
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                exposure_readback_ui(ui, readback.left);
+            });
+        });
+        config_section_header(
+            "Right Mono Camera",
+            depthai::ConfigSection::RightCamera,
+            focused_section,
+        )
+        .show(ui, |ui| {
+            ui.vertical(|ui| {
+                if ui
+                    .checkbox(&mut device_config.right_camera.enabled, "Enabled")
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Resolution: ");
+                    egui::ComboBox::from_id_source("right_camera_resolution")
+                        .width(combo_width(ui, depthai::MonoCameraResolution::ALL))
+                        .selected_text(format!("{}", device_config.right_camera.resolution))
+                        .show_ui(ui, |ui| {
+                            for resolution in depthai::MonoCameraResolution::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.right_camera.resolution,
+                                        resolution,
+                                        resolution.to_string(),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                let max_fps = device_config.right_camera.resolution.max_fps();
+                if fps_ui(ui, &mut device_config.right_camera.fps, max_fps) {
+                    update_device_config = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Socket: ");
+                    egui::ComboBox::from_id_source("right_camera_socket")
+                        .width(combo_width(
+                            ui,
+                            depthai::BoardSocket::MONO.map(|socket| format!("{socket:?}")),
+                        ))
+                        .selected_text(format!("{:?}", device_config.right_camera.board_socket))
+                        .show_ui(ui, |ui| {
+                            for socket in depthai::BoardSocket::MONO {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.right_camera.board_socket,
+                                        socket,
+                                        format!("{socket:?}"),
+                                    )
+                                    .changed()
+                                {
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Orientation: ");
+                    egui::ComboBox::from_id_source("right_camera_orientation")
+                        .width(combo_width(ui, depthai::CameraImageOrientation::ALL))
+                        .selected_text(format!("{}", device_config.right_camera.orientation))
+                        .show_ui(ui, |ui| {
+                            for orientation in depthai::CameraImageOrientation::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut device_config.right_camera.orientation,
+                                        orientation,
+                                        format!("{orientation}"),
+                                    )
+                                    .changed()
+                                {
+                                    // Depth needs both mono cameras in the same orientation
+                                    device_config.left_camera.orientation = orientation;
+                                    update_device_config = true;
+                                }
+                            }
+                        });
+                });
+                exposure_readback_ui(ui, readback.right);
+            });
+        });
+        update_device_config
+    }
+
+    /// Returns true if `device_config` changed.
+    fn depth_config_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device_config: &mut depthai::DeviceConfig,
+        focused_section: Option<depthai::ConfigSection>,
+    ) -> bool {
+        if !self.ctx.depthai_state.selected_device.has_stereo() {
+            ui.weak(format!(
+                "The {} has no stereo cameras, depth isn't available",
+                self.ctx.depthai_state.selected_device.model
+            ));
+            return false;
+        }
+        let mut update_device_config = false;
+        let mut depth = device_config.depth.unwrap_or_default();
+        let depth_validation = device_config.validate_depth();
+        ui.horizontal(|ui| {
+            let depth_enabled = &mut self.ctx.depthai_state.device_config.config.depth_enabled;
+            // Only turning depth on needs compatible mono cameras, the reason is shown either way.
+            let response = ui.add_enabled(
+                depth_validation.is_ok() || *depth_enabled,
+                egui::Checkbox::new(depth_enabled, "Depth"),
+            );
+            if let Err(err) = &depth_validation {
+                response.on_disabled_hover_text(err.to_string());
+                ui.colored_label(ui.visuals().warn_fg_color, err.to_string());
+                if *err == depthai::ConfigError::MonoResolutionMismatch
+                    && ui
+                        .small_button("Sync resolutions")
+                        .on_hover_text("Use the left mono camera's resolution for the right one")
+                        .clicked()
+                {
+                    device_config.right_camera.resolution = device_config.left_camera.resolution;
+                    update_device_config = true;
+                }
+            }
+        });
+        if self.ctx.depthai_state.device_config.config.depth_enabled {
+            config_section_header("Depth", depthai::ConfigSection::Depth, focused_section).show(
+                ui,
+                |ui| {
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
-                            ui.label("Resolution: ");
-                            egui::ComboBox::from_id_source("color_camera_resolution")
-                                .width(70.0)
-                                .selected_text(format!("{}", device_config.color_camera.resolution))
+                            ui.label("Align to: ");
+                            egui::ComboBox::from_id_source("depth_align")
+                                .width(combo_width(ui, ["Color", "Right mono"]))
+                                .selected_text(match depth.align {
+                                    depthai::BoardSocket::RGB => "Color",
+                                    _ => "Right mono",
+                                })
+                                .show_ui(ui, |ui| {
+                                    for (align, label) in [
+                                        (depthai::BoardSocket::RGB, "Color"),
+                                        (depthai::BoardSocket::RIGHT, "Right mono"),
+                                    ] {
+                                        if ui
+                                            .selectable_value(&mut depth.align, align, label)
+                                            .changed()
+                                        {
+                                            update_device_config = true;
+                                            device_config.depth = Some(depth);
+                                        }
+                                    }
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Preset: ");
+                            egui::ComboBox::from_id_source("depth_preset")
+                                .width(combo_width(ui, depthai::DepthProfilePreset::ALL))
+                                .selected_text(depth.preset.to_string())
+                                .show_ui(ui, |ui| {
+                                    for preset in depthai::DepthProfilePreset::ALL {
+                                        if ui
+                                            .selectable_value(
+                                                &mut depth.preset,
+                                                preset,
+                                                preset.to_string(),
+                                            )
+                                            .on_hover_text(preset.description())
+                                            .changed()
+                                        {
+                                            update_device_config = true;
+                                            device_config.depth = Some(depth);
+                                        }
+                                    }
+                                })
+                                .response
+                                .on_hover_text(depth.preset.description());
+                            ui.hyperlink_to("ⓘ", DEPTH_PRESET_DOCS_URL)
+                                .on_hover_text("Stereo depth presets in the DepthAI docs");
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Decimation: ");
+                            egui::ComboBox::from_id_source("depth_decimation_factor")
+                                .width(combo_width(ui, (1..=4).map(decimation_label)))
+                                .selected_text(decimation_label(depth.decimation_factor))
                                 .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut device_config.color_camera.resolution,
-                                            depthai::ColorCameraResolution::THE_1080_P,
-                                            "1080p",
-                                        )
-                                        .changed()
-                                    {
-                                        update_device_config = true;
+                                    for factor in 1..=4 {
+                                        if ui
+                                            .selectable_value(
+                                                &mut depth.decimation_factor,
+                                                factor,
+                                                decimation_label(factor),
+                                            )
+                                            .changed()
+                                        {
+                                            update_device_config = true;
+                                            device_config.depth = Some(depth);
+                                        }
                                     }
-                                    if ui
-                                        .selectable_value(
-                                            &mut device_config.color_camera.resolution,
-                                            depthai::ColorCameraResolution::THE_4_K,
-                                            "4k",
-                                        )
-                                        .changed()
-                                    {
-                                        update_device_config = true;
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Median: ");
+                            egui::ComboBox::from_id_source("depth_median_filter")
+                                .width(combo_width(ui, depthai::DepthMedianFilter::ALL))
+                                .selected_text(depth.median.to_string())
+                                .show_ui(ui, |ui| {
+                                    for median in depthai::DepthMedianFilter::ALL {
+                                        if ui
+                                            .selectable_value(
+                                                &mut depth.median,
+                                                median,
+                                                median.to_string(),
+                                            )
+                                            .changed()
+                                        {
+                                            update_device_config = true;
+                                            device_config.depth = Some(depth);
+                                        }
                                     }
                                 });
                         });
                         ui.horizontal(|ui| {
-                            ui.label("FPS: ");
-                            if ui
-                                .add(egui::DragValue::new(&mut device_config.color_camera.fps))
-                                .changed()
+                            ui.label("Confidence: ");
+                            if numeric_entry_ui(
+                                ui,
+                                &mut depth.confidence_threshold,
+                                0..=u8::MAX,
+                                "",
+                            )
+                            .on_hover_text("Lower values discard more of the less confident pixels")
+                            .changed()
                             {
                                 update_device_config = true;
+                                device_config.depth = Some(depth);
                             }
                         });
-                    });
-                });
-                ui.collapsing("Left Mono Camera", |ui| {
-                    ui.vertical(|ui| {
                         ui.horizontal(|ui| {
-                            ui.label("Resolution: ");
-                            egui::ComboBox::from_id_source("left_camera_resolution")
-                                .width(70.0)
-                                .selected_text(format!("{}", device_config.left_camera.resolution))
-                                .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut device_config.left_camera.resolution,
-                                            depthai::MonoCameraResolution::THE_400_P,
-                                            "400p",
-                                        )
-                                        .changed()
-                                    {
-                                        update_device_config = true;
-                                    }
-                                });
+                            ui.label("Disparity shift: ");
+                            if numeric_entry_ui(
+                                ui,
+                                &mut depth.disparity_shift,
+                                0..=depthai::DepthConfig::MAX_DISPARITY_SHIFT,
+                                " px",
+                            )
+                            .on_hover_text(
+                                "Shifts the disparity search range to see objects closer than \
+                                about 30 cm. The further the shift, the closer the minimum depth, \
+                                but the maximum depth shrinks too: far away objects get no depth.",
+                            )
+                            .changed()
+                            {
+                                update_device_config = true;
+                                device_config.depth = Some(depth);
+                            }
+                        });
+                        ui.collapsing("Filters", |ui| {
+                            let filters = &mut depth.filters;
+                            let mut changed = false;
+                            changed |= ui.checkbox(&mut filters.spatial, "Spatial").changed();
+                            changed |= ui.checkbox(&mut filters.temporal, "Temporal").changed();
+                            changed |= ui.checkbox(&mut filters.speckle, "Speckle").changed();
+                            ui.horizontal(|ui| {
+                                ui.label("Threshold: ");
+                                let max = filters.threshold_max;
+                                changed |= numeric_entry_ui(
+                                    ui,
+                                    &mut filters.threshold_min,
+                                    0..=max,
+                                    " mm",
+                                )
+                                .changed();
+                                ui.label("-");
+                                let min = filters.threshold_min;
+                                changed |= numeric_entry_ui(
+                                    ui,
+                                    &mut filters.threshold_max,
+                                    min..=u16::MAX,
+                                    " mm",
+                                )
+                                .changed();
+                            });
+                            if changed {
+                                update_device_config = true;
+                                device_config.depth = Some(depth);
+                            }
                         });
                         ui.horizontal(|ui| {
-                            ui.label("FPS: ");
                             if ui
-                                .add(egui::DragValue::new(&mut device_config.left_camera.fps))
+                                .checkbox(&mut depth.stream_disparity, "Show Disparity")
+                                .on_hover_text("Also stream the raw disparity map")
                                 .changed()
                             {
                                 update_device_config = true;
+                                device_config.depth = Some(depth);
                             }
                         });
-                    });
-                });
-                ui.collapsing("Right Mono Camera", |ui| {
-                    ui.vertical(|ui| {
                         ui.horizontal(|ui| {
-                            ui.label("Resolution: ");
-                            egui::ComboBox::from_id_source("right_camera_resolution")
-                                .width(70.0)
-                                .selected_text(format!("{}", device_config.right_camera.resolution))
-                                .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut device_config.right_camera.resolution,
-                                            depthai::MonoCameraResolution::THE_400_P,
-                                            "400p",
-                                        )
-                                        .changed()
-                                    {
-                                        update_device_config = true;
-                                    }
-                                });
+                            if ui
+                                .checkbox(&mut depth.stream_confidence, "Show Confidence")
+                                .on_hover_text(
+                                    "Also stream the per pixel stereo confidence, \
+                                    to see where depth is unreliable",
+                                )
+                                .changed()
+                            {
+                                update_device_config = true;
+                                device_config.depth = Some(depth);
+                            }
                         });
                         ui.horizontal(|ui| {
-                            ui.label("FPS: ");
                             if ui
-                                .add(egui::DragValue::new(&mut device_config.right_camera.fps))
+                                .checkbox(&mut depth.pointcloud.enabled, "Point Cloud")
                                 .changed()
                             {
                                 update_device_config = true;
+                                device_config.depth = Some(depth);
                             }
                         });
-                    });
-                });
-                ui.checkbox(
-                    &mut self.ctx.depthai_state.device_config.config.depth_enabled,
-                    "Depth",
-                );
-                if self.ctx.depthai_state.device_config.config.depth_enabled {
-                    ui.collapsing("Depth", |ui| {
-                        ui.vertical(|ui| {
+                        if depth.pointcloud.enabled {
                             ui.horizontal(|ui| {
+                                ui.label("Voxel size: ");
                                 if ui
-                                    .checkbox(&mut depth.pointcloud.enabled, "Point Cloud")
+                                    .add(
+                                        egui::DragValue::new(&mut depth.pointcloud.voxel_size_m)
+                                            .clamp_range(
+                                                depthai::PointcloudConfig::VOXEL_SIZE_RANGE_M,
+                                            )
+                                            .speed(0.001)
+                                            .suffix(" m"),
+                                    )
+                                    .on_hover_text("Downsample the point cloud, 0 = off")
                                     .changed()
                                 {
                                     update_device_config = true;
                                     device_config.depth = Some(depth);
                                 }
                             });
-                        });
+                            if let Some(point_count) = self
+                                .ctx
+                                .depthai_state
+                                .device_stats
+                                .as_ref()
+                                .and_then(|stats| stats.approximate_point_count)
+                            {
+                                ui.label(format!("≈ {point_count} points"));
+                            }
+                            ui.weak(format!("From depth: {}", depth.summary()))
+                                .on_hover_text(
+                                    "The depth settings the point cloud is computed from. \
+                                    Subpixel and left-right check follow the preset.",
+                                );
+                        }
                     });
-                    if device_config.depth.is_none() {
-                        device_config.depth = Some(depth);
-                        update_device_config = true;
-                    }
-                } else {
-                    if device_config.depth.is_some() {
-                        device_config.depth = None;
-                        update_device_config = true;
+                },
+            );
+            if device_config.depth.is_none() {
+                device_config.depth = Some(depth);
+                update_device_config = true;
+            }
+        } else {
+            if device_config.depth.is_some() {
+                device_config.depth = None;
+                update_device_config = true;
+            }
+        }
+        update_device_config
+    }
+
+    /// Returns true if `device_config` changed.
+    fn ai_config_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        device_config: &mut depthai::DeviceConfig,
+        focused_section: Option<depthai::ConfigSection>,
+    ) -> bool {
+        let mut update_device_config = false;
+        let depth_enabled = device_config.depth.is_some();
+        ui.vertical(|ui| {
+            if focused_section == Some(depthai::ConfigSection::AiModel) {
+                ui.scroll_to_cursor(Some(egui::Align::Center));
+            }
+            ui.label("AI Model:");
+            egui::ComboBox::from_id_source("ai_model_selection")
+                .width(combo_width(
+                    ui,
+                    self.ctx
+                        .depthai_state
+                        .neural_networks
+                        .iter()
+                        .map(|nn| nn.display_name.as_str()),
+                ))
+                .selected_text(format!("{}", device_config.ai_model.display_name))
+                .show_ui(ui, |ui| {
+                    for nn in self.ctx.depthai_state.neural_networks.iter() {
+                        if ui
+                            .selectable_value(
+                                &mut device_config.ai_model,
+                                nn.clone(),
+                                &nn.display_name,
+                            )
+                            .changed()
+                        {
+                            update_device_config = true;
+                        }
+                    }
+                });
+            ui.add_enabled_ui(!device_config.ai_model.path.is_empty(), |ui| {
+                if ui
+                    .add(
+                        egui::Slider::new(
+                            &mut device_config.ai_config.confidence_threshold,
+                            0.0..=1.0,
+                        )
+                        .text("Confidence"),
+                    )
+                    .changed()
+                {
+                    update_device_config = true;
+                }
+                ui.add_enabled_ui(depth_enabled, |ui| {
+                    if ui
+                        .checkbox(&mut device_config.ai_config.spatial, "Spatial")
+                        .on_hover_text(
+                            "Locate the detections in 3D using the depth map, \
+                            shown as boxes in the 3D view",
+                        )
+                        .on_disabled_hover_text(
+                            "Spatial detections take their distance from the depth map, \
+                            enable Depth to use them",
+                        )
+                        .changed()
+                    {
+                        update_device_config = true;
                     }
-                }
-                ui.vertical(|ui| {
-                    ui.label("AI Model:");
-                    egui::ComboBox::from_id_source("ai_model_selection")
-                        .width(70.0)
-                        .selected_text(format!("{}", device_config.ai_model.display_name))
-                        .show_ui(ui, |ui| {
-                            for nn in self.ctx.depthai_state.neural_networks.iter() {
-                                if ui
-                                    .selectable_value(
-                                        &mut device_config.ai_model,
-                                        nn.clone(),
-                                        &nn.display_name,
-                                    )
-                                    .changed()
-                                {
-                                    update_device_config = true;
-                                }
-                            }
-                        });
                 });
             });
-            if update_device_config {
-                self.ctx.depthai_state.set_device_config(&mut device_config);
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.button("Load custom model…").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Neural network", &["blob", "json"])
+                    .pick_file()
+                {
+                    self.ctx.depthai_state.add_custom_neural_network(&path);
+                }
             }
+            ui.collapsing("Manage models", |ui| {
+                let neural_networks = &self.ctx.depthai_state.neural_networks;
+                let num_neural_networks = neural_networks.len();
+                let mut swap = None;
+                let mut remove = None;
+                for (i, nn) in neural_networks.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(i > 0, egui::Button::new("⏶")).clicked() {
+                            swap = Some((i - 1, i));
+                        }
+                        if ui
+                            .add_enabled(i + 1 < num_neural_networks, egui::Button::new("⏷"))
+                            .clicked()
+                        {
+                            swap = Some((i, i + 1));
+                        }
+                        ui.label(&nn.display_name).on_hover_text(&nn.path);
+                        if !nn.is_builtin()
+                            && self
+                                .ctx
+                                .re_ui
+                                .small_icon_button(ui, &re_ui::icons::REMOVE)
+                                .on_hover_text("Remove model")
+                                .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some((a, b)) = swap {
+                    self.ctx.depthai_state.neural_networks.swap(a, b);
+                }
+                if let Some(i) = remove {
+                    self.ctx.depthai_state.remove_neural_network(i);
+                }
+            });
         });
+        update_device_config
+    }
+
+    /// Unsubscribe from IMU data if subscribed and not shown in any space view
+    fn unsubscribe_hidden_imu(&mut self) {
+        if !self.ctx.depthai_state.is_streaming_paused()
+            && !self.ctx.depthai_state.imu_visible
+            && self
+                .ctx
+                .depthai_state
+                .subscriptions
+                .contains(&depthai::ChannelId::ImuData)
+        {
+            let mut subs = self
+                .ctx
+                .depthai_state
+                .subscriptions
+                .iter()
+                .filter_map(|x| {
+                    if x != &depthai::ChannelId::ImuData {
+                        return Some(x.clone());
+                    } else {
+                        return None;
+                    }
+                })
+                .collect_vec();
+            self.ctx.depthai_state.set_subscriptions(&subs);
+            self.accel_history.clear();
+            self.gyro_history.clear();
+            self.magnetometer_history.clear();
+        }
+    }
+
+    /// The IMU plots, keeps the IMU subscribed while they are shown.
+    fn imu_stats_ui(&mut self, ui: &mut egui::Ui) {
+        // Subscribe to IMU data if not already subscribed
+        if !self.ctx.depthai_state.is_streaming_paused()
+            && !self
+                .ctx
+                .depthai_state
+                .subscriptions
+                .contains(&depthai::ChannelId::ImuData)
+        {
+            let mut subs = self.ctx.depthai_state.subscriptions.clone();
+            subs.push(depthai::ChannelId::ImuData);
+            self.ctx.depthai_state.set_subscriptions(&subs);
+        }
+        ui.checkbox(
+            &mut self.ctx.depthai_state.log_imu_scalars,
+            "Log to the timeline",
+        )
+        .on_hover_text(
+            "Also log every IMU axis as a scalar under imu_data, \
+            to plot it in time series views next to the rest of the data",
+        );
+        self.stats_ui(ui);
     }
 
     fn stats_ui(&mut self, ui: &mut egui::Ui) {
@@ -306,16 +1814,34 @@ impl<'a, 'b> DepthaiTabs<'a, 'b> {
     }
 
     fn xyz_plot_ui(&mut self, ui: &mut egui::Ui, kind: ImuTabKind) {
-        let (history, display_name, unit) = match kind {
-            ImuTabKind::Accel => (&mut self.accel_history, "Accelerometer", "(m/s^2)"),
-            ImuTabKind::Gyro => (&mut self.gyro_history, "Gyroscope", "(rad/s)"),
-            ImuTabKind::Mag => (&mut self.magnetometer_history, "Magnetometer", "(uT)"),
+        let (history, following, display_name, unit) = match kind {
+            ImuTabKind::Accel => (
+                &mut self.accel_history,
+                &mut self.accel_following,
+                "Accelerometer",
+                "(m/s^2)",
+            ),
+            ImuTabKind::Gyro => (
+                &mut self.gyro_history,
+                &mut self.gyro_following,
+                "Gyroscope",
+                "(rad/s)",
+            ),
+            ImuTabKind::Mag => (
+                &mut self.magnetometer_history,
+                &mut self.magnetometer_following,
+                "Magnetometer",
+                "(uT)",
+            ),
         };
         let Some(latest) = history.latest() else {
         ui.label(format!("No {display_name} data yet"));
         return;
     };
-        ui.label(display_name);
+        ui.horizontal(|ui| {
+            ui.label(display_name);
+            following.ui(ui, history);
+        });
         ui.add_sized([ui.available_width(), 150.0], |ui: &mut egui::Ui| {
             ui.vertical(|ui| {
                 DockArea::new(&mut self.imu_accel_tabs)
@@ -325,6 +1851,7 @@ impl<'a, 'b> DepthaiTabs<'a, 'b> {
                         ui,
                         &mut ImuXyzTabs {
                             data: *history,
+                            following: *following,
                             kind: kind,
                         },
                     );
@@ -339,53 +1866,343 @@ impl<'a, 'b> DepthaiTabs<'a, 'b> {
     }
 }
 
+fn device_stats_ui(ui: &mut egui::Ui, stats: &depthai::DeviceStats) {
+    ui.horizontal(|ui| {
+        let temperature = format!("🌡 {:.1} °C", stats.temperature_c);
+        if stats.temperature_c >= depthai::DeviceStats::HIGH_TEMPERATURE_C {
+            ui.colored_label(ui.visuals().warn_fg_color, temperature)
+                .on_hover_text("The device may be thermal throttling");
+        } else {
+            ui.label(temperature);
+        }
+        ui.label(format!("CPU {:.0}%", stats.cpu_usage * 100.0));
+        ui.label(format!("Mem {:.0}%", stats.mem_usage * 100.0));
+    });
+}
+
+/// `slowest_fps` is the frame rate of the slowest camera, streams only line up to a frame of it.
+fn frame_sync_ui(ui: &mut egui::Ui, frame_sync: &depthai::FrameSync, slowest_fps: Option<u8>) {
+    let (Some(skew_secs), Some(slowest_fps)) =
+        (frame_sync.max_skew_secs(instant::Instant::now()), slowest_fps)
+    else {
+        return;
+    };
+    let skew = format!("⏱ Max frame skew {:.0} ms", skew_secs * 1e3);
+    let response = if skew_secs > depthai::FrameSync::warning_secs(slowest_fps) {
+        ui.colored_label(ui.visuals().warn_fg_color, skew)
+    } else {
+        ui.label(skew)
+    };
+    response.on_hover_text(format!(
+        "Largest difference between the timestamps of the latest frames of the subscribed \
+        streams over the last {:.0} s. Streams running at different FPS differ by up to a frame.",
+        depthai::FrameSync::WINDOW_SECS
+    ));
+}
+
+/// FPS entry limited to what the sensor supports at the selected resolution.
+///
+/// Returns true if the fps changed, also when it was lowered to fit a new resolution.
+fn fps_ui(ui: &mut egui::Ui, fps: &mut u8, max_fps: u8) -> bool {
+    let mut changed = false;
+    if *fps > max_fps {
+        *fps = max_fps;
+        changed = true;
+    }
+    ui.horizontal(|ui| {
+        ui.label("FPS: ");
+        changed |= ui
+            .add(egui::DragValue::new(fps).clamp_range(depthai::MIN_FPS..=max_fps))
+            .changed();
+        ui.weak(format!("{}-{max_fps}", depthai::MIN_FPS));
+    });
+    changed
+}
+
+/// Read-only exposure the camera is running with, nothing if the device doesn't report it.
+fn exposure_readback_ui(ui: &mut egui::Ui, exposure: Option<depthai::CameraExposure>) {
+    let Some(exposure) = exposure else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.label("Current: ");
+        let mut text = format!("{} µs, ISO {}", exposure.exposure_us, exposure.iso);
+        if let Some(white_balance_k) = exposure.white_balance_k {
+            text += &format!(", {white_balance_k} K");
+        }
+        ui.weak(text)
+            .on_hover_text("What the camera is running with right now, also in auto mode");
+    });
+}
+
+/// Whether we're talking to the backend, with a way to reconnect when we're not.
+fn backend_connection_ui(ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+    if depthai_state.playback {
+        return;
+    }
+    ui.horizontal(|ui| {
+        if depthai_state.is_backend_connected() {
+            ui.label("● Backend connected");
+        } else {
+            ui.colored_label(ui.visuals().error_fg_color, "● Backend disconnected");
+            if ui
+                .button("Reconnect backend")
+                .on_hover_text("Connect to the backend again, e.g. after restarting it")
+                .clicked()
+            {
+                depthai_state.reconnect_backend();
+            }
+        }
+    });
+}
+
+/// Freeze all streams at once, e.g. to look at the last frames, without reconfiguring the device.
+fn streaming_pause_ui(ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+    if depthai_state.playback || depthai_state.selected_device.id == "" {
+        return;
+    }
+    ui.horizontal(|ui| {
+        let paused = depthai_state.is_streaming_paused();
+        if paused {
+            if ui
+                .button("▶ Resume streaming")
+                .on_hover_text("Stream the channels that were subscribed before pausing")
+                .clicked()
+            {
+                depthai_state.resume_streaming();
+            }
+        } else if ui
+            .button("⏸ Pause streaming")
+            .on_hover_text("Stop all streams, the device keeps its configuration")
+            .clicked()
+        {
+            depthai_state.pause_streaming();
+        }
+        if ui
+            .add_enabled(paused, egui::Button::new("⏭ Step"))
+            .on_hover_text("Get the next frame of every paused stream")
+            .on_disabled_hover_text("Pause streaming to step through single frames")
+            .clicked()
+        {
+            depthai_state.step_frame();
+        }
+        if paused {
+            ui.colored_label(ui.visuals().warn_fg_color, "⏸ Streaming paused");
+        }
+    });
+}
+
+/// The neural network running on the device and what it runs on, click to show its configuration.
+fn active_model_ui(ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+    let config = &depthai_state.device_config.config;
+    if config.ai_model.path.is_empty() {
+        return;
+    }
+    let response = ui.horizontal(|ui| {
+        let model = ui.add(
+            egui::Label::new(format!("🧠 {}", config.ai_model.display_name))
+                .sense(egui::Sense::click()),
+        );
+        // The network always runs on the color camera preview.
+        if config.color_camera.enabled {
+            ui.weak("on Color Camera");
+        } else {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "no input, enable the Color Camera",
+            );
+        }
+        model
+    });
+    if response
+        .inner
+        .on_hover_text("Click to show the neural network configuration")
+        .clicked()
+    {
+        depthai_state.focused_config_section = Some(depthai::ConfigSection::AiModel);
+    }
+}
+
+/// `value` as read-only, pretty printed JSON under `label`.
+fn raw_json_ui<T: serde::Serialize>(ui: &mut egui::Ui, label: &str, value: &T) {
+    ui.label(label);
+    let json = serde_json::to_string_pretty(value).unwrap_or_else(|err| err.to_string());
+    // Editing a `&str` is a no-op, the text can still be selected and copied.
+    ui.add(
+        egui::TextEdit::multiline(&mut json.as_str())
+            .code_editor()
+            .desired_width(f32::INFINITY),
+    );
+}
+
+/// JSON `text` as it went over the wire, pretty printed if it parses.
+fn raw_json_text_ui(ui: &mut egui::Ui, label: &str, text: &str) {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) => raw_json_ui(ui, label, &value),
+        Err(_) => {
+            ui.label(label);
+            ui.add(
+                egui::TextEdit::multiline(&mut &*text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY),
+            );
+        }
+    }
+}
+
+/// Every stream that is subscribed to at a glance, click one to show its configuration.
+fn subscriptions_summary_ui(ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+    if depthai_state.selected_device.id == "" {
+        return;
+    }
+    let mut focused_section = None;
+    ui.collapsing(
+        format!("Subscribed streams ({})", depthai_state.subscriptions.len()),
+        |ui| {
+            if depthai_state.subscriptions.is_empty() {
+                ui.weak("Nothing is streamed");
+                return;
+            }
+            egui::Grid::new("subscriptions_summary")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for channel in depthai_state
+                        .subscriptions
+                        .iter()
+                        .sorted_by_key(|channel| **channel as u8)
+                    {
+                        let mut label = ui.add(
+                            egui::Label::new(format!("{channel:?}")).sense(egui::Sense::click()),
+                        );
+                        if let Some(section) = channel.config_section() {
+                            label = label.on_hover_text("Click to show its configuration");
+                            if label.clicked() {
+                                focused_section = Some(section);
+                            }
+                        }
+                        match depthai_state.channel_bandwidth.get(channel) {
+                            Some(bytes_per_sec) => ui.label(format!(
+                                "{}/s",
+                                re_format::format_bytes(*bytes_per_sec as _)
+                            )),
+                            None => ui.weak("No data yet"),
+                        };
+                        ui.end_row();
+                    }
+                });
+        },
+    );
+    if focused_section.is_some() {
+        depthai_state.focused_config_section = focused_section;
+    }
+}
+
+/// Copy the current config as json for bug reports, or paste one to reproduce someone's setup.
+fn config_clipboard_ui(ui: &mut egui::Ui, depthai_state: &mut depthai::State) {
+    ui.horizontal(|ui| {
+        if ui
+            .small_button("Copy config JSON")
+            .on_hover_text("Copy the device configuration to the clipboard")
+            .clicked()
+        {
+            match serde_json::to_string_pretty(&depthai_state.device_config.config) {
+                Ok(json) => ui.output_mut(|o| o.copied_text = json),
+                Err(err) => re_log::error!("Failed to serialize the device config: {err}"),
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui
+            .add_enabled(
+                depthai_state.selected_device.id != "" && !depthai_state.playback,
+                egui::Button::new("Paste config JSON").small(),
+            )
+            .on_hover_text("Apply a device configuration copied with \"Copy config JSON\"")
+            .clicked()
+        {
+            if let Some(json) = crate::misc::Clipboard::with(|clipboard| clipboard.get_text()) {
+                depthai_state.paste_device_config(&json);
+            }
+        }
+    });
+}
+
+/// A [`egui::DragValue`] for exact values: click to type one in, or drag to step through them.
+///
+/// Typed values that aren't a whole number within `range` are rejected,
+/// leaving the last valid value in place instead of clamping it to something the user didn't ask for.
+fn numeric_entry_ui<Num: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    value: &mut Num,
+    range: std::ops::RangeInclusive<Num>,
+    suffix: &str,
+) -> egui::Response {
+    use egui::emath::Numeric as _;
+
+    let (min, max) = (range.start().to_f64(), range.end().to_f64());
+    ui.add(
+        egui::DragValue::new(value)
+            .clamp_range(range)
+            .speed(1.0)
+            .suffix(suffix)
+            .custom_parser(move |text| {
+                let parsed = text.trim().parse::<i64>().ok()? as f64;
+                (min..=max).contains(&parsed).then_some(parsed)
+            }),
+    )
+}
+
+/// Width for a combo box that fits its widest option, so labels aren't clipped at any text size
+/// and the combo doesn't change size when another option is selected.
+fn combo_width<S: ToString>(ui: &egui::Ui, options: impl IntoIterator<Item = S>) -> f32 {
+    const MIN_WIDTH: f32 = 70.0;
+    const MAX_WIDTH: f32 = 240.0;
+    let font_id = egui::TextStyle::Button.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let text_width = ui.fonts(|fonts| {
+        options
+            .into_iter()
+            .map(|option| {
+                fonts
+                    .layout_no_wrap(option.to_string(), font_id.clone(), color)
+                    .size()
+                    .x
+            })
+            .fold(0.0, f32::max)
+    });
+    let spacing = ui.spacing();
+    // Room for the dropdown icon and the padding around the text.
+    let width =
+        text_width + spacing.icon_spacing + spacing.icon_width + 2.0 * spacing.button_padding.x;
+    width.clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+fn decimation_label(factor: u8) -> String {
+    if factor <= 1 {
+        "Off".to_owned()
+    } else {
+        format!("{factor}x")
+    }
+}
+
+/// Opens the header if its section was focused from elsewhere, e.g. from the node graph.
+fn config_section_header(
+    title: &str,
+    section: depthai::ConfigSection,
+    focused_section: Option<depthai::ConfigSection>,
+) -> egui::CollapsingHeader {
+    egui::CollapsingHeader::new(title).open((focused_section == Some(section)).then_some(true))
+}
+
 impl<'a, 'b> egui_dock::TabViewer for DepthaiTabs<'a, 'b> {
     type Tab = String;
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
         match tab.as_str() {
             "Configuration" => {
-                // Unsubscribe from IMU data if subscribed
-                if self
-                    .ctx
-                    .depthai_state
-                    .subscriptions
-                    .contains(&depthai::ChannelId::ImuData)
-                {
-                    let mut subs = self
-                        .ctx
-                        .depthai_state
-                        .subscriptions
-                        .iter()
-                        .filter_map(|x| {
-                            if x != &depthai::ChannelId::ImuData {
-                                return Some(x.clone());
-                            } else {
-                                return None;
-                            }
-                        })
-                        .collect_vec();
-                    self.ctx.depthai_state.set_subscriptions(&subs);
-                    self.accel_history.clear();
-                    self.gyro_history.clear();
-                    self.magnetometer_history.clear();
-                }
+                self.unsubscribe_hidden_imu();
                 self.device_configuration_ui(ui);
             }
-            "Stats" => {
-                // Subscribe to IMU data if not already subscribed
-                if !self
-                    .ctx
-                    .depthai_state
-                    .subscriptions
-                    .contains(&depthai::ChannelId::ImuData)
-                {
-                    let mut subs = self.ctx.depthai_state.subscriptions.clone();
-                    subs.push(depthai::ChannelId::ImuData);
-                    self.ctx.depthai_state.set_subscriptions(&subs);
-                }
-                self.stats_ui(ui);
-            }
+            "Stats" => self.imu_stats_ui(ui),
             _ => {}
         }
     }
@@ -403,6 +2220,9 @@ pub(crate) struct SelectionPanel {
     depthai_tabs: Tree<String>,
     #[serde(skip)]
     imu_accel_tabs: Tree<XYZ>,
+    config_layout: ConfigLayout,
+    #[serde(skip)]
+    config_tabs: Tree<ConfigTab>,
     #[serde(skip)]
     accel_history: History<[f32; 3]>,
     #[serde(skip)]
@@ -410,6 +2230,12 @@ pub(crate) struct SelectionPanel {
     #[serde(skip)]
     magnetometer_history: History<[f32; 3]>,
     #[serde(skip)]
+    accel_following: PlotFollowing<[f32; 3]>,
+    #[serde(skip)]
+    gyro_following: PlotFollowing<[f32; 3]>,
+    #[serde(skip)]
+    magnetometer_following: PlotFollowing<[f32; 3]>,
+    #[serde(skip)]
     start_time: instant::Instant,
 }
 
@@ -418,9 +2244,14 @@ impl Default for SelectionPanel {
         Self {
             depthai_tabs: DepthaiTabs::tree(),
             imu_accel_tabs: ImuXyzTabs::tree(),
+            config_layout: ConfigLayout::default(),
+            config_tabs: ConfigTab::tree(),
             accel_history: History::new(0..1000, 5.0),
             gyro_history: History::new(0..1000, 5.0),
             magnetometer_history: History::new(0..1000, 5.0),
+            accel_following: Default::default(),
+            gyro_following: Default::default(),
+            magnetometer_following: Default::default(),
             start_time: instant::Instant::now(),
         }
     }
@@ -465,8 +2296,16 @@ impl SelectionPanel {
                             ui.vertical(|ui| {
                                 ui.horizontal(|ui| {
                                     ui.label("Device: ");
+                                    let width = combo_width(
+                                        ui,
+                                        available_devices
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(i, device)| format!("{}: {device}", i + 1))
+                                            .chain(["No device selected".to_owned()]),
+                                    );
                                     egui::ComboBox::from_id_source("device")
-                                        .width(70.0)
+                                        .width(width)
                                         .selected_text(if combo_device != "" {
                                             combo_device.clone().to_string()
                                         } else {
@@ -483,12 +2322,20 @@ impl SelectionPanel {
                                             {
                                                 ctx.depthai_state.set_device(combo_device.clone());
                                             }
-                                            for device in available_devices {
+                                            for (i, device) in
+                                                available_devices.into_iter().enumerate()
+                                            {
+                                                // The first nine can be selected with Ctrl+1..9.
+                                                let label = if i < 9 {
+                                                    format!("{}: {device}", i + 1)
+                                                } else {
+                                                    device.to_string()
+                                                };
                                                 if ui
                                                     .selectable_value(
                                                         &mut combo_device,
                                                         device.clone().to_string(),
-                                                        device.to_string(),
+                                                        label,
                                                     )
                                                     .changed()
                                                 {
@@ -497,8 +2344,54 @@ impl SelectionPanel {
                                                 }
                                             }
                                         });
+                                    if !ctx.depthai_state.selected_device.model.is_empty() {
+                                        ui.weak(&ctx.depthai_state.selected_device.model);
+                                    }
+                                    ui.add_enabled_ui(
+                                        ctx.depthai_state.selected_device.id != "",
+                                        |ui| {
+                                            ui.menu_button("Reset to defaults", |ui| {
+                                                ui.label("This restarts the pipeline.");
+                                                ui.horizontal(|ui| {
+                                                    if ui.button("Reset").clicked() {
+                                                        ctx.depthai_state.reset_device_config();
+                                                        ui.close_menu();
+                                                    }
+                                                    if ui.button("Cancel").clicked() {
+                                                        ui.close_menu();
+                                                    }
+                                                });
+                                            });
+                                            ui.menu_button("Disconnect", |ui| {
+                                                ui.label(
+                                                    "Release the device so other programs can use it.",
+                                                );
+                                                ui.horizontal(|ui| {
+                                                    if ui.button("Disconnect").clicked() {
+                                                        ctx.depthai_state.disconnect_device();
+                                                        ui.close_menu();
+                                                    }
+                                                    if ui.button("Cancel").clicked() {
+                                                        ui.close_menu();
+                                                    }
+                                                });
+                                            });
+                                        },
+                                    );
                                 });
 
+                                backend_connection_ui(ui, ctx.depthai_state);
+                                // In the tabbed layout the stats have their own pane
+                                if self.config_layout == ConfigLayout::List {
+                                    if let Some(stats) = &ctx.depthai_state.device_stats {
+                                        device_stats_ui(ui, stats);
+                                    }
+                                }
+                                active_model_ui(ui, ctx.depthai_state);
+                                subscriptions_summary_ui(ui, ctx.depthai_state);
+                                streaming_pause_ui(ui, ctx.depthai_state);
+                                config_clipboard_ui(ui, ctx.depthai_state);
+
                                 if ctx.depthai_state.device_config.update_in_progress {
                                     ui.add_sized(
                                         [ui.available_width(), 50.0],
@@ -515,23 +2408,34 @@ impl SelectionPanel {
                                 egui::ScrollArea::both()
                                     .auto_shrink([false; 2])
                                     .show(ui, |ui| {
-                                        DockArea::new(&mut self.depthai_tabs)
-                                            .id(egui::Id::new("depthai_tabs"))
-                                            .style(re_ui::egui_dock_style(ui.style()))
-                                            .show_inside(
-                                                ui,
-                                                &mut DepthaiTabs {
-                                                    ctx,
-                                                    accel_history: &mut self.accel_history,
-                                                    gyro_history: &mut self.gyro_history,
-                                                    magnetometer_history: &mut self
-                                                        .magnetometer_history,
-                                                    now: self.start_time.elapsed().as_nanos()
-                                                        as f64
-                                                        / 1e9,
-                                                    imu_accel_tabs: &mut self.imu_accel_tabs,
-                                                },
-                                            );
+                                        let config_layout = self.config_layout;
+                                        let mut depthai_tabs = DepthaiTabs {
+                                            ctx,
+                                            accel_history: &mut self.accel_history,
+                                            gyro_history: &mut self.gyro_history,
+                                            magnetometer_history: &mut self.magnetometer_history,
+                                            accel_following: &mut self.accel_following,
+                                            gyro_following: &mut self.gyro_following,
+                                            magnetometer_following: &mut self
+                                                .magnetometer_following,
+                                            now: self.start_time.elapsed().as_nanos() as f64
+                                                / 1e9,
+                                            imu_accel_tabs: &mut self.imu_accel_tabs,
+                                            config_layout: &mut self.config_layout,
+                                            config_tabs: &mut self.config_tabs,
+                                        };
+                                        match config_layout {
+                                            ConfigLayout::List => {
+                                                DockArea::new(&mut self.depthai_tabs)
+                                                    .id(egui::Id::new("depthai_tabs"))
+                                                    .style(re_ui::egui_dock_style(ui.style()))
+                                                    .show_inside(ui, &mut depthai_tabs);
+                                            }
+                                            // The panes are docked straight into the header
+                                            ConfigLayout::Tabs => {
+                                                depthai_tabs.device_configuration_ui(ui);
+                                            }
+                                        }
                                     });
                             });
                         });
@@ -790,7 +2694,7 @@ fn blueprint_ui(
                     // splat - the whole entity
                     let data_blueprint = space_view.data_blueprint.data_blueprints_individual();
                     let mut props = data_blueprint.get(&instance_path.entity_path);
-                    entity_props_ui(
+                    let distance_for_all_pinholes = entity_props_ui(
                         ctx,
                         ui,
                         Some(&instance_path.entity_path),
@@ -798,6 +2702,13 @@ fn blueprint_ui(
                         &space_view.view_state,
                     );
                     data_blueprint.set(instance_path.entity_path.clone(), props);
+                    if let Some(distance) = distance_for_all_pinholes {
+                        set_pinhole_image_plane_distances(
+                            ctx,
+                            &mut space_view.data_blueprint,
+                            distance,
+                        );
+                    }
                 }
             } else {
                 list_existing_data_blueprints(ui, ctx, &instance_path.entity_path, blueprint);
@@ -856,13 +2767,16 @@ fn list_existing_data_blueprints(
     }
 }
 
+/// Returns the image plane distance that should be applied to every pinhole of the space view,
+/// if the user asked for it.
 fn entity_props_ui(
     ctx: &mut ViewerContext<'_>,
     ui: &mut egui::Ui,
     entity_path: Option<&EntityPath>,
     entity_props: &mut EntityProperties,
     view_state: &ViewState,
-) {
+) -> Option<f32> {
+    let mut distance_for_all_pinholes = None;
     ui.checkbox(&mut entity_props.visible, "Visible");
     ui.checkbox(&mut entity_props.interactive, "Interactive")
         .on_hover_text("If disabled, the entity will not react to any mouse interaction");
@@ -897,13 +2811,19 @@ fn entity_props_ui(
             }
             ui.end_row();
 
+            if let Some(entity_path) = entity_path {
+                image_props_ui(ctx, ui, entity_path, entity_props);
+            }
+
             if *view_state.state_spatial.nav_mode.get() == SpatialNavigationMode::ThreeD {
                 if let Some(entity_path) = entity_path {
-                    pinhole_props_ui(ctx, ui, entity_path, entity_props);
+                    distance_for_all_pinholes =
+                        pinhole_props_ui(ctx, ui, entity_path, entity_props);
                     depth_props_ui(ctx, ui, entity_path, entity_props);
                 }
             }
         });
+    distance_for_all_pinholes
 }
 
 fn colormap_props_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
@@ -922,7 +2842,7 @@ fn colormap_props_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
                     .selectable_label(current == proposed, proposed.to_string())
                     .clicked()
                 {
-                    entity_props.color_mapper = EditableAutoValue::Auto(proposed);
+                    entity_props.color_mapper = EditableAutoValue::UserEdited(proposed);
                 }
             };
 
@@ -937,31 +2857,163 @@ fn colormap_props_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
     ui.end_row();
 }
 
+/// Single channel images, e.g. the depth confidence, can be colormapped too.
+fn image_props_ui(
+    ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    entity_path: &EntityPath,
+    entity_props: &mut EntityProperties,
+) -> Option<()> {
+    let query = ctx.current_query();
+    let tensor = query_latest_single::<Tensor>(&ctx.log_db.entity_db, entity_path, &query)?;
+    let [_, _, channels] = tensor.image_height_width_channels()?;
+    if tensor.meaning != TensorDataMeaning::Unknown || channels != 1 {
+        return Some(());
+    }
+
+    // These are shown in grayscale until the user picks something else.
+    if entity_props.color_mapper.is_auto() {
+        entity_props.color_mapper =
+            EditableAutoValue::Auto(ColorMapper::Colormap(Colormap::Grayscale));
+    }
+    colormap_props_ui(ui, entity_props);
+    Some(())
+}
+
+/// Returns the distance if it should be applied to all pinholes.
 fn pinhole_props_ui(
     ctx: &mut ViewerContext<'_>,
     ui: &mut egui::Ui,
     entity_path: &EntityPath,
     entity_props: &mut EntityProperties,
-) {
+) -> Option<f32> {
     let query = ctx.current_query();
-    if let Some(re_log_types::Transform::Pinhole(_)) =
+    let mut apply_to_all = false;
+    if let Some(re_log_types::Transform::Pinhole(pinhole)) =
         query_latest_single::<Transform>(&ctx.log_db.entity_db, entity_path, &query)
     {
+        // The extrinsics live on the parent, the pinhole itself only holds the intrinsics.
+        let extrinsics = entity_path.parent().and_then(|parent| {
+            match query_latest_single::<Transform>(&ctx.log_db.entity_db, &parent, &query) {
+                Some(re_log_types::Transform::Rigid3(rigid3)) => Some(rigid3),
+                _ => None,
+            }
+        });
+        calibration_ui(ui, &pinhole, extrinsics.as_ref());
+
         ui.label("Image plane distance");
         let mut distance = *entity_props.pinhole_image_plane_distance.get();
         let speed = (distance * 0.05).at_least(0.01);
-        if ui
-            .add(
-                egui::DragValue::new(&mut distance)
-                    .clamp_range(0.0..=1.0e8)
-                    .speed(speed),
-            )
-            .on_hover_text("Controls how far away the image plane is.")
-            .changed()
-        {
-            entity_props.pinhole_image_plane_distance = EditableAutoValue::UserEdited(distance);
-        }
+        ui.horizontal(|ui| {
+            if ui
+                .add(
+                    egui::DragValue::new(&mut distance)
+                        .clamp_range(0.0..=1.0e8)
+                        .speed(speed),
+                )
+                .on_hover_text("Controls how far away the image plane is.")
+                .changed()
+            {
+                entity_props.pinhole_image_plane_distance = EditableAutoValue::UserEdited(distance);
+            }
+            apply_to_all = ui
+                .button("Apply to all")
+                .on_hover_text("Use this distance for every pinhole camera in the Space View.")
+                .clicked();
+        });
         ui.end_row();
+        if apply_to_all {
+            return Some(distance);
+        }
+    }
+    None
+}
+
+/// Read-only view of the calibration a pinhole camera was logged with.
+fn calibration_ui(
+    ui: &mut egui::Ui,
+    pinhole: &re_log_types::Pinhole,
+    extrinsics: Option<&re_log_types::Rigid3>,
+) {
+    ui.label("Intrinsics");
+    egui::Grid::new("pinhole_intrinsics")
+        .num_columns(2)
+        .show(ui, |ui| {
+            let focal_length = pinhole.focal_length_in_pixels();
+            let principal_point = pinhole.principal_point();
+            for (name, value) in [
+                ("fx", focal_length.x()),
+                ("fy", focal_length.y()),
+                ("cx", principal_point.x),
+                ("cy", principal_point.y),
+            ] {
+                ui.label(name);
+                ui.monospace(format!("{value:.3}"));
+                ui.end_row();
+            }
+            if let Some(resolution) = pinhole.resolution() {
+                ui.label("resolution");
+                ui.monospace(format!("{}x{}", resolution.x, resolution.y));
+                ui.end_row();
+            }
+        });
+    ui.end_row();
+
+    ui.label("Extrinsics");
+    if let Some(extrinsics) = extrinsics {
+        let pose = extrinsics.parent_from_child();
+        let translation = pose.translation();
+        let (x, y, z) = pose.rotation().to_euler(glam::EulerRot::XYZ);
+        egui::Grid::new("pinhole_extrinsics")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("translation");
+                ui.monospace(format!(
+                    "{:.3}, {:.3}, {:.3}",
+                    translation.x, translation.y, translation.z
+                ));
+                ui.end_row();
+
+                ui.label("rotation");
+                ui.monospace(format!(
+                    "{:.2}°, {:.2}°, {:.2}°",
+                    x.to_degrees(),
+                    y.to_degrees(),
+                    z.to_degrees()
+                ))
+                .on_hover_text("Euler angles around X, Y and Z, applied in that order");
+                ui.end_row();
+            });
+    } else {
+        ui.weak("(none)");
+    }
+    ui.end_row();
+}
+
+/// Sets the same image plane distance on every pinhole entity of the space view.
+fn set_pinhole_image_plane_distances(
+    ctx: &mut ViewerContext<'_>,
+    data_blueprint: &mut DataBlueprintTree,
+    distance: f32,
+) {
+    let query = ctx.current_query();
+    let pinholes = data_blueprint
+        .entity_paths()
+        .iter()
+        .filter(|entity_path| {
+            matches!(
+                query_latest_single::<Transform>(&ctx.log_db.entity_db, entity_path, &query),
+                Some(re_log_types::Transform::Pinhole(_))
+            )
+        })
+        .cloned()
+        .collect_vec();
+
+    let data_blueprints = data_blueprint.data_blueprints_individual();
+    for entity_path in pinholes {
+        let mut props = data_blueprints.get(&entity_path);
+        props.pinhole_image_plane_distance = EditableAutoValue::UserEdited(distance);
+        data_blueprints.set(entity_path, props);
     }
 }
 
@@ -978,6 +3030,10 @@ fn depth_props_ui(
     if tensor.meaning != TensorDataMeaning::Depth {
         return Some(());
     }
+
+    // Applies both to the depth image and to the backprojected point cloud.
+    colormap_props_ui(ui, entity_props);
+
     let pinhole_ent_path =
         crate::misc::queries::closest_pinhole_transform(ctx, entity_path, &query)?;
 
@@ -1003,19 +3059,54 @@ fn depth_props_ui(
             );
         ui.end_row();
 
-        depth_from_world_scale_ui(ui, &mut entity_props.depth_from_world_scale);
+        depth_unit_ui(ui, entity_props);
+
+        depth_from_world_scale_ui(
+            ui,
+            &mut entity_props.depth_from_world_scale,
+            *entity_props.depth_unit.get(),
+        );
 
-        backproject_radius_scale_ui(ui, &mut entity_props.backproject_radius_scale);
+        backproject_radius_ui(ui, entity_props);
 
-        // TODO(cmc): This should apply to the depth map entity as a whole, but for that we
-        // need to get the current hardcoded colormapping out of the image cache first.
-        colormap_props_ui(ui, entity_props);
+        point_cloud_render_fraction_ui(ui, &mut entity_props.point_cloud_render_fraction);
     }
 
     Some(())
 }
 
-fn depth_from_world_scale_ui(ui: &mut egui::Ui, property: &mut EditableAutoValue<f32>) {
+fn depth_unit_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
+    let current = *entity_props.depth_unit.get();
+
+    ui.label("Depth unit");
+    egui::ComboBox::from_id_source("depth_unit")
+        .selected_text(current.to_string())
+        .show_ui(ui, |ui| {
+            for unit in [DepthUnit::Millimeters, DepthUnit::Meters] {
+                if ui
+                    .selectable_label(current == unit, unit.to_string())
+                    .clicked()
+                {
+                    entity_props.depth_unit = EditableAutoValue::UserEdited(unit);
+                    // Let the scale follow the new unit.
+                    entity_props.depth_from_world_scale =
+                        EditableAutoValue::Auto(unit.steps_per_meter());
+                }
+            }
+        })
+        .response
+        .on_hover_text(format!(
+            "The depth image values are in {}, a wrong unit scales the point cloud by 1000",
+            current.to_string().to_lowercase()
+        ));
+    ui.end_row();
+}
+
+fn depth_from_world_scale_ui(
+    ui: &mut egui::Ui,
+    property: &mut EditableAutoValue<f32>,
+    depth_unit: DepthUnit,
+) {
     ui.label("Backproject meter");
     let mut value = *property.get();
     let speed = (value * 0.05).at_least(0.01);
@@ -1023,7 +3114,8 @@ fn depth_from_world_scale_ui(ui: &mut egui::Ui, property: &mut EditableAutoValue
     .add(
         egui::DragValue::new(&mut value)
             .clamp_range(0.0..=1.0e8)
-            .speed(speed),
+            .speed(speed)
+            .suffix(format!(" {}/m", depth_unit.symbol())),
     )
     .on_hover_text("How many steps in the depth image correspond to one world-space unit. For instance, 1000 means millimeters.\n\
                     Double-click to reset.");
@@ -1037,6 +3129,75 @@ fn depth_from_world_scale_ui(ui: &mut egui::Ui, property: &mut EditableAutoValue
     ui.end_row();
 }
 
+fn point_cloud_render_fraction_ui(ui: &mut egui::Ui, fraction: &mut f32) {
+    ui.label("Rendered points");
+    let response = ui
+        .add(
+            egui::Slider::new(fraction, 0.01..=1.0)
+                .custom_formatter(|fraction, _| format!("{:.0}%", fraction * 100.0)),
+        )
+        .on_hover_text(
+            "Only render this fraction of the backprojected points, \
+            to keep the view interactive on slower machines.\n\
+            The logged depth data is left untouched.\n\
+            Double-click to reset.",
+        );
+    if response.double_clicked() {
+        *fraction = 1.0;
+        response.surrender_focus();
+    }
+    ui.end_row();
+}
+
+fn backproject_radius_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
+    ui.label("Point radius");
+    ui.horizontal(|ui| {
+        ui.radio_value(
+            &mut entity_props.backproject_radius_mode,
+            BackprojectRadiusMode::Scale,
+            "Scale",
+        )
+        .on_hover_text("Points grow with their depth, to cover the pixel they came from");
+        ui.radio_value(
+            &mut entity_props.backproject_radius_mode,
+            BackprojectRadiusMode::Meters,
+            "Meters",
+        )
+        .on_hover_text("Every point has the same physical size");
+    });
+    ui.end_row();
+
+    match entity_props.backproject_radius_mode {
+        BackprojectRadiusMode::Scale => {
+            backproject_radius_scale_ui(ui, &mut entity_props.backproject_radius_scale);
+        }
+        BackprojectRadiusMode::Meters => {
+            backproject_radius_m_ui(ui, &mut entity_props.backproject_radius_m);
+        }
+    }
+}
+
+fn backproject_radius_m_ui(ui: &mut egui::Ui, radius_m: &mut f32) {
+    ui.label("Backproject radius");
+    let response = ui
+        .add(
+            egui::DragValue::new(radius_m)
+                .clamp_range(0.0..=1.0)
+                .speed(0.0005)
+                .min_decimals(3)
+                .suffix(" m"),
+        )
+        .on_hover_text(
+            "Radius of every point in the backprojected point cloud.\n\
+            Double-click to reset.",
+        );
+    if response.double_clicked() {
+        *radius_m = EntityProperties::default().backproject_radius_m;
+        response.surrender_focus();
+    }
+    ui.end_row();
+}
+
 fn backproject_radius_scale_ui(ui: &mut egui::Ui, property: &mut EditableAutoValue<f32>) {
     ui.label("Backproject radius scale");
     let mut value = *property.get();
@@ -38,5 +38,21 @@ impl SceneNodeGraph {
         let store = &ctx.log_db.entity_db.data_store;
 
         for entity_path in query.entity_paths {}
+
+        // The backend logs don't go through the store, they're shown alongside the pipeline.
+        let backend_path = EntityPath::from("depthai/backend");
+        self.NodeGraph_entries
+            .extend(
+                ctx.depthai_state
+                    .backend_logs
+                    .iter()
+                    .map(|entry| NodeGraphEntry {
+                        entity_path: backend_path.clone(),
+                        time: Some((entry.timestamp * 1e9) as i64),
+                        color: None,
+                        level: Some(entry.level.clone()),
+                        body: entry.message.clone(),
+                    }),
+            );
     }
 }
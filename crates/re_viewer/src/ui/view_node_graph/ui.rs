@@ -5,7 +5,7 @@ use egui::{Color32, RichText};
 use re_data_store::{EntityPath, Timeline};
 use re_log_types::TimePoint;
 
-use crate::ViewerContext;
+use crate::{depthai::depthai, ui::view_text::level_to_rich_text, ViewerContext};
 
 use super::{NodeGraphEntry, SceneNodeGraph};
 // --- Main view ---
@@ -27,7 +27,44 @@ pub struct ViewNodeGraphState {
 impl ViewNodeGraphState {
     pub fn selection_ui(&mut self, re_ui: &re_ui::ReUi, ui: &mut egui::Ui) {
         crate::profile_function!();
-        re_log::info!("Holda from node graph");
+
+        let ViewNodeGraphFilters {
+            col_timelines,
+            col_entity_path,
+            col_log_level,
+            row_entity_paths,
+            row_log_levels,
+        } = &mut self.filters;
+
+        re_ui
+            .selection_grid(ui, "node_graph_config")
+            .show(ui, |ui| {
+                re_ui.grid_left_hand_label(ui, "Columns");
+                ui.vertical(|ui| {
+                    for (timeline, visible) in col_timelines {
+                        ui.checkbox(visible, timeline.name().to_string());
+                    }
+                    ui.checkbox(col_entity_path, "Entity path");
+                    ui.checkbox(col_log_level, "Log level");
+                });
+                ui.end_row();
+
+                re_ui.grid_left_hand_label(ui, "Entity Filter");
+                ui.vertical(|ui| {
+                    for (entity_path, visible) in row_entity_paths {
+                        ui.checkbox(visible, &entity_path.to_string());
+                    }
+                });
+                ui.end_row();
+
+                re_ui.grid_left_hand_label(ui, "Level Filter");
+                ui.vertical(|ui| {
+                    for (log_level, visible) in row_log_levels {
+                        ui.checkbox(visible, level_to_rich_text(ui, log_level));
+                    }
+                });
+                ui.end_row();
+            });
     }
 }
 
@@ -39,12 +76,235 @@ pub(crate) fn view_node_graph(
 ) -> egui::Response {
     crate::profile_function!();
 
-    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-        if ui.button("Button text").clicked() {
-            re_log::info!("Holda from node graph");
+    // Update filters if necessary.
+    state.filters.update(ctx, &scene.NodeGraph_entries);
+
+    let graph = PipelineGraph::from_config(&ctx.depthai_state.device_config.config);
+
+    let entries = scene
+        .NodeGraph_entries
+        .iter()
+        .filter(|entry| {
+            state.filters.is_entity_path_visible(&entry.entity_path)
+                && entry
+                    .level
+                    .as_ref()
+                    .map_or(true, |level| state.filters.is_log_level_visible(level))
+        })
+        .collect::<Vec<_>>();
+
+    // Leave room for the backend logs below the graph, if there are any.
+    let mut graph_size = ui.available_size();
+    if !entries.is_empty() {
+        graph_size.y *= 0.6;
+    }
+    let (response, painter) = ui.allocate_painter(graph_size, egui::Sense::hover());
+    let rect = response.rect;
+    let node_rects = graph.layout(rect);
+
+    let edge_stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+    for (from, to) in &graph.edges {
+        painter.line_segment(
+            [
+                node_rects[*from].right_center(),
+                node_rects[*to].left_center(),
+            ],
+            edge_stroke,
+        );
+    }
+
+    for (i, (node, node_rect)) in graph.nodes.iter().zip(&node_rects).enumerate() {
+        let node_response = ui.interact(*node_rect, response.id.with(i), egui::Sense::click());
+        let visuals = ui.style().interact(&node_response);
+        painter.rect(
+            *node_rect,
+            visuals.rounding,
+            visuals.bg_fill,
+            visuals.bg_stroke,
+        );
+        painter.text(
+            node_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            &node.label,
+            egui::TextStyle::Button.resolve(ui.style()),
+            visuals.text_color(),
+        );
+
+        if let Some(section) = node.section {
+            if node_response
+                .on_hover_text("Click to show this node's configuration")
+                .clicked()
+            {
+                ctx.depthai_state.focused_config_section = Some(section);
+            }
         }
-    })
-    .response
+    }
+
+    if !entries.is_empty() {
+        ui.separator();
+        node_graph_entries_ui(ui, state, &entries);
+    }
+
+    response
+}
+
+fn node_graph_entries_ui(
+    ui: &mut egui::Ui,
+    state: &ViewNodeGraphState,
+    entries: &[&NodeGraphEntry],
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for entry in entries {
+                ui.horizontal(|ui| {
+                    if state.filters.col_log_level {
+                        if let Some(level) = &entry.level {
+                            ui.label(level_to_rich_text(ui, level));
+                        }
+                    }
+                    if state.filters.col_entity_path {
+                        ui.label(entry.entity_path.to_string());
+                    }
+                    let body = RichText::new(&entry.body);
+                    ui.label(if state.monospace {
+                        body.monospace()
+                    } else {
+                        body
+                    });
+                });
+            }
+        });
+}
+
+// --- Pipeline graph ---
+
+/// A single node of the depthai pipeline.
+struct PipelineNode {
+    label: String,
+
+    /// Nodes are laid out left-to-right by column: sources, processing, outputs.
+    column: usize,
+
+    /// The device configuration section this node is configured in, if any.
+    section: Option<depthai::ConfigSection>,
+}
+
+/// The depthai pipeline, as derived from the current [`depthai::DeviceConfig`].
+#[derive(Default)]
+struct PipelineGraph {
+    nodes: Vec<PipelineNode>,
+
+    /// Links between nodes, as indices into `nodes`.
+    edges: Vec<(usize, usize)>,
+}
+
+impl PipelineGraph {
+    const NUM_COLUMNS: usize = 3;
+    const NODE_SIZE: egui::Vec2 = egui::vec2(120.0, 32.0);
+
+    fn from_config(config: &depthai::DeviceConfig) -> Self {
+        use depthai::ConfigSection;
+
+        let mut graph = Self::default();
+
+        let mut add_camera = |enabled: bool, label, section, output| {
+            if !enabled {
+                return None;
+            }
+            let camera = graph.add_node(label, 0, Some(section));
+            let camera_out = graph.add_node(output, 2, None);
+            graph.edges.push((camera, camera_out));
+            Some(camera)
+        };
+        let color = add_camera(
+            config.color_camera.enabled,
+            "Color Camera",
+            ConfigSection::ColorCamera,
+            "Color",
+        );
+        let left = add_camera(
+            config.left_camera.enabled,
+            "Left Mono Camera",
+            ConfigSection::LeftCamera,
+            "Left Mono",
+        );
+        let right = add_camera(
+            config.right_camera.enabled,
+            "Right Mono Camera",
+            ConfigSection::RightCamera,
+            "Right Mono",
+        );
+
+        if let Some(depth) = config.depth {
+            let stereo = graph.add_node("Stereo Depth", 1, Some(ConfigSection::Depth));
+            for mono in [left, right].into_iter().flatten() {
+                graph.edges.push((mono, stereo));
+            }
+
+            let depth_out = graph.add_node("Depth", 2, None);
+            graph.edges.push((stereo, depth_out));
+            if depth.pointcloud.enabled {
+                let pointcloud_out = graph.add_node("Point Cloud", 2, None);
+                graph.edges.push((stereo, pointcloud_out));
+            }
+        }
+
+        if !config.ai_model.path.is_empty() {
+            // Show which model is running, not just that there is one.
+            let nn = graph.add_node(
+                config.ai_model.display_name.clone(),
+                1,
+                Some(ConfigSection::AiModel),
+            );
+            if let Some(color) = color {
+                graph.edges.push((color, nn));
+            }
+
+            let detections_out = graph.add_node("Detections", 2, None);
+            graph.edges.push((nn, detections_out));
+        }
+
+        graph
+    }
+
+    fn add_node(
+        &mut self,
+        label: impl Into<String>,
+        column: usize,
+        section: Option<depthai::ConfigSection>,
+    ) -> usize {
+        self.nodes.push(PipelineNode {
+            label: label.into(),
+            column,
+            section,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Lays out the nodes left-to-right inside `rect`, spacing the nodes of each column evenly.
+    fn layout(&self, rect: egui::Rect) -> Vec<egui::Rect> {
+        let mut num_rows = [0; Self::NUM_COLUMNS];
+        for node in &self.nodes {
+            num_rows[node.column] += 1;
+        }
+
+        let column_width = rect.width() / Self::NUM_COLUMNS as f32;
+        let mut row = [0; Self::NUM_COLUMNS];
+        self.nodes
+            .iter()
+            .map(|node| {
+                let row_height = rect.height() / num_rows[node.column] as f32;
+                let center = egui::pos2(
+                    rect.left() + column_width * (node.column as f32 + 0.5),
+                    rect.top() + row_height * (row[node.column] as f32 + 0.5),
+                );
+                row[node.column] += 1;
+                egui::Rect::from_center_size(center, Self::NODE_SIZE)
+            })
+            .collect()
+    }
 }
 
 // --- Filters ---
@@ -92,5 +352,28 @@ impl ViewNodeGraphFilters {
     // accordingly.
     fn update(&mut self, ctx: &mut ViewerContext<'_>, NodeGraph_entries: &[NodeGraphEntry]) {
         crate::profile_function!();
+
+        let Self {
+            col_timelines,
+            col_entity_path: _,
+            col_log_level: _,
+            row_entity_paths,
+            row_log_levels,
+        } = self;
+
+        for timeline in ctx.log_db.timelines() {
+            col_timelines.entry(*timeline).or_insert(true);
+        }
+
+        for entity_path in NodeGraph_entries.iter().map(|entry| &entry.entity_path) {
+            row_entity_paths.entry(entity_path.clone()).or_insert(true);
+        }
+
+        for level in NodeGraph_entries
+            .iter()
+            .filter_map(|entry| entry.level.as_ref())
+        {
+            row_log_levels.entry(level.clone()).or_insert(true);
+        }
     }
 }
@@ -68,8 +68,14 @@ pub struct Image {
     /// up to a ~65m range.
     pub meter: Option<f32>,
 
+    /// The unit depth values are shown in.
+    pub depth_unit: re_data_store::DepthUnit,
+
     /// A thing that provides additional semantic context for your dtype.
     pub annotations: Arc<Annotations>,
+
+    /// The color map the image is shown with, if any.
+    pub color_mapper: Option<re_data_store::ColorMapper>,
 }
 
 pub enum UiLabelTarget {
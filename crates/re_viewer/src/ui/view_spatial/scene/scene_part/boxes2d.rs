@@ -8,6 +8,7 @@ use re_query::{query_primary_with_history, EntityView, QueryError};
 use re_renderer::Size;
 
 use crate::{
+    depthai::depthai,
     misc::{SpaceViewHighlights, TransformCache, ViewerContext},
     ui::{
         scene::SceneQuery,
@@ -32,6 +33,12 @@ impl Boxes2DPart {
     ) -> Result<(), QueryError> {
         scene.num_logged_2d_objects += 1;
 
+        // Detections are drawn over the color image they were detected in, each with its label.
+        let is_detections = depthai::device_channel_from_entity_path(ent_path)
+            .map_or(false, |(_, channel)| {
+                channel == depthai::ChannelId::Detections
+            });
+
         let annotations = scene.annotation_map.find(ent_path);
         let default_color = DefaultColor::EntityPath(ent_path);
 
@@ -63,7 +70,10 @@ impl Boxes2DPart {
                 let color =
                     annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
                 let radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
-                let label = annotation_info.label(label.map(|l| l.0).as_ref());
+                let mut label = annotation_info.label(label.map(|l| l.0).as_ref());
+                if is_detections {
+                    label = Some(depthai::detection_label(label, class_id.map(|id| id.0)));
+                }
 
                 let rectangle = line_batch
                     .add_rectangle_outline_2d(
@@ -9,6 +9,7 @@ use re_query::{query_primary_with_history, EntityView, QueryError};
 use re_renderer::Size;
 
 use crate::{
+    depthai::depthai,
     misc::{SpaceViewHighlights, SpaceViewOutlineMasks, TransformCache, ViewerContext},
     ui::{
         scene::SceneQuery,
@@ -31,6 +32,12 @@ impl Boxes3DPart {
     ) -> Result<(), QueryError> {
         scene.num_logged_3d_objects += 1;
 
+        // Spatial detections are in camera space, so their position gives the distance to the camera.
+        let show_distance = depthai::device_channel_from_entity_path(ent_path)
+            .map_or(false, |(_, channel)| {
+                channel == depthai::ChannelId::SpatialDetections
+            });
+
         let annotations = scene.annotation_map.find(ent_path);
         let default_color = DefaultColor::EntityPath(ent_path);
         let mut line_batch = scene
@@ -75,7 +82,12 @@ impl Boxes3DPart {
                 box_lines.outline_mask_ids(*outline_mask_ids);
             }
 
-            if let Some(label) = annotation_info.label(label.as_ref().map(|s| &s.0)) {
+            let mut label = annotation_info.label(label.as_ref().map(|s| &s.0));
+            if show_distance {
+                let distance = format!("{:.2} m", tran.length());
+                label = Some(label.map_or(distance.clone(), |label| format!("{label} {distance}")));
+            }
+            if let Some(label) = label {
                 scene.ui.labels.push(UiLabel {
                     text: label,
                     target: UiLabelTarget::Position3D(world_from_obj.transform_point3(tran)),
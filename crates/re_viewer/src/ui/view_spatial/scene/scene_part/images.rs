@@ -2,7 +2,7 @@ use egui::NumExt;
 use glam::Vec3;
 use itertools::Itertools;
 
-use re_data_store::{query_latest_single, EntityPath, EntityProperties};
+use re_data_store::{query_latest_single, BackprojectRadiusMode, EntityPath, EntityProperties};
 use re_log_types::{
     component_types::{ColorRGBA, InstanceKey, Tensor, TensorData, TensorDataMeaning},
     Component, Transform,
@@ -10,7 +10,7 @@ use re_log_types::{
 use re_query::{query_primary_with_history, EntityView, QueryError};
 use re_renderer::{
     renderer::{DepthCloud, DepthCloudDepthData, RectangleOptions},
-    Colormap, OutlineMaskPreference,
+    OutlineMaskPreference,
 };
 
 use crate::{
@@ -24,6 +24,17 @@ use crate::{
 
 use super::ScenePart;
 
+/// The color map an image is shown with, if any.
+///
+/// Depth is always colormapped, other single channel images only if the user picked a colormap.
+fn image_color_mapper(
+    tensor: &Tensor,
+    properties: &EntityProperties,
+) -> Option<re_data_store::ColorMapper> {
+    (tensor.meaning == TensorDataMeaning::Depth || !properties.color_mapper.is_auto())
+        .then(|| *properties.color_mapper.get())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn push_tensor_texture(
     scene: &mut SceneSpatial,
@@ -34,6 +45,7 @@ fn push_tensor_texture(
     tensor: &Tensor,
     multiplicative_tint: egui::Rgba,
     outline_mask: OutlineMaskPreference,
+    properties: &EntityProperties,
 ) {
     crate::profile_function!();
 
@@ -48,6 +60,7 @@ fn push_tensor_texture(
         tensor,
         tensor_stats,
         annotations,
+        image_color_mapper(tensor, properties),
     ) {
         Ok(colormapped_texture) => {
             let textured_rect = re_renderer::renderer::TexturedRect {
@@ -133,6 +146,14 @@ fn handle_image_layering(scene: &mut SceneSpatial) {
     }
 }
 
+/// Every `step`th pixel of every `step`th row of a `width` wide image.
+fn decimate<T: Copy>(data: &[T], width: usize, step: usize) -> Vec<T> {
+    data.chunks(width)
+        .step_by(step)
+        .flat_map(|row| row.iter().step_by(step).copied())
+        .collect()
+}
+
 pub(crate) struct ImagesPart;
 
 impl ImagesPart {
@@ -163,12 +184,22 @@ impl ImagesPart {
                 let annotations = scene.annotation_map.find(ent_path);
 
                 // TODO(jleibs): Meter should really be its own component
-                let meter = tensor.meter;
+                // The depth scale follows the unit chosen in the selection panel, and is
+                // only unset before the depth heuristics first ran.
+                let meter = if tensor.meaning == TensorDataMeaning::Depth {
+                    Some(*properties.depth_from_world_scale.get())
+                        .filter(|meter| *meter > 0.0)
+                        .or(tensor.meter)
+                } else {
+                    tensor.meter
+                };
                 scene.ui.images.push(Image {
                     ent_path: ent_path.clone(),
                     tensor: tensor.clone(),
                     meter,
+                    depth_unit: *properties.depth_unit.get(),
                     annotations: annotations.clone(),
+                    color_mapper: image_color_mapper(tensor, properties),
                 });
 
                 let entity_highlight = highlights.entity_outline_mask(ent_path.hash());
@@ -204,6 +235,7 @@ impl ImagesPart {
                 Self::process_entity_view_as_image(
                     scene,
                     ctx,
+                    properties,
                     ent_path,
                     world_from_obj,
                     entity_highlight,
@@ -221,6 +253,7 @@ impl ImagesPart {
     fn process_entity_view_as_image(
         scene: &mut SceneSpatial,
         ctx: &mut ViewerContext<'_>,
+        properties: &EntityProperties,
         ent_path: &EntityPath,
         world_from_obj: glam::Mat4,
         entity_highlight: &SpaceViewOutlineMasks,
@@ -246,6 +279,7 @@ impl ImagesPart {
                     &tensor,
                     color.into(),
                     entity_highlight.overall,
+                    properties,
                 );
             }
             Err(err) => {
@@ -288,11 +322,19 @@ impl ImagesPart {
             return Err(format!("Couldn't fetch pinhole extrinsics at {pinhole_ent_path:?}"));
         };
 
+        let (h, w) = (tensor.shape()[0].size, tensor.shape()[1].size);
+
+        // Render every `step`th pixel in both directions, so roughly `1 / step²` of the points.
+        let step = (1.0 / properties.point_cloud_render_fraction.max(0.01).sqrt()).round() as usize;
+        let step = step.max(1);
+
         // TODO(cmc): automagically convert as needed for non-natively supported datatypes?
         let data = match &tensor.data {
             // NOTE: Shallow clone if feature `arrow` is enabled, full alloc + memcpy otherwise.
-            TensorData::U16(data) => DepthCloudDepthData::U16(data.clone()),
-            TensorData::F32(data) => DepthCloudDepthData::F32(data.clone()),
+            TensorData::U16(data) if step == 1 => DepthCloudDepthData::U16(data.clone()),
+            TensorData::F32(data) if step == 1 => DepthCloudDepthData::F32(data.clone()),
+            TensorData::U16(data) => DepthCloudDepthData::U16(decimate(data, w, step).into()),
+            TensorData::F32(data) => DepthCloudDepthData::F32(decimate(data, w, step).into()),
             _ => {
                 return Err(format!(
                     "Tensor datatype {} is not supported for backprojection",
@@ -304,27 +346,43 @@ impl ImagesPart {
         let depth_from_world_scale = *properties.depth_from_world_scale.get();
         let world_depth_from_data_depth = 1.0 / depth_from_world_scale;
 
-        let (h, w) = (tensor.shape()[0].size, tensor.shape()[1].size);
         let dimensions = glam::UVec2::new(w as _, h as _);
 
-        let colormap = match *properties.color_mapper.get() {
-            re_data_store::ColorMapper::Colormap(colormap) => match colormap {
-                re_data_store::Colormap::Grayscale => Colormap::Grayscale,
-                re_data_store::Colormap::Turbo => Colormap::Turbo,
-                re_data_store::Colormap::Viridis => Colormap::Viridis,
-                re_data_store::Colormap::Plasma => Colormap::Plasma,
-                re_data_store::Colormap::Magma => Colormap::Magma,
-                re_data_store::Colormap::Inferno => Colormap::Inferno,
-            },
-        };
+        // The depth may have been decimated after the intrinsics were computed,
+        // in which case they need to be scaled down to the actual depth resolution.
+        let mut depth_camera_intrinsics: glam::Mat3 = intrinsics.image_from_cam.into();
+        if let Some(resolution) = intrinsics.resolution() {
+            let scale = dimensions.as_vec2() / resolution;
+            if scale != glam::Vec2::ONE {
+                depth_camera_intrinsics =
+                    glam::Mat3::from_diagonal(scale.extend(1.0)) * depth_camera_intrinsics;
+            }
+        }
+
+        // The decimated depth is a lower resolution image of the same camera.
+        let step = step as u32;
+        let dimensions = (dimensions + step - 1) / step;
+        let h = dimensions.y;
+        depth_camera_intrinsics =
+            glam::Mat3::from_diagonal(Vec3::new(1.0 / step as f32, 1.0 / step as f32, 1.0))
+                * depth_camera_intrinsics;
+
+        let colormap = crate::gpu_bridge::re_renderer_colormap(*properties.color_mapper.get());
 
         // We want point radius to be defined in a scale where the radius of a point
         // is a factor (`backproject_radius_scale`) of the diameter of a pixel projected
         // at that distance.
         let fov_y = intrinsics.fov_y().unwrap_or(1.0);
         let pixel_width_from_depth = (0.5 * fov_y).tan() / (0.5 * h as f32);
-        let radius_scale = *properties.backproject_radius_scale.get();
-        let point_radius_from_world_depth = radius_scale * pixel_width_from_depth;
+        let (point_radius_from_world_depth, point_radius_in_world) =
+            match properties.backproject_radius_mode {
+                BackprojectRadiusMode::Scale => {
+                    let radius_scale = *properties.backproject_radius_scale.get();
+                    (radius_scale * pixel_width_from_depth, 0.0)
+                }
+                // World-space depth is in meters, see `depth_from_world_scale`.
+                BackprojectRadiusMode::Meters => (0.0, properties.backproject_radius_m),
+            };
 
         let max_data_value = if let Some((_min, max)) = ctx.cache.tensor_stats(tensor).range {
             max as f32
@@ -340,9 +398,10 @@ impl ImagesPart {
 
         scene.primitives.depth_clouds.clouds.push(DepthCloud {
             world_from_obj,
-            depth_camera_intrinsics: intrinsics.image_from_cam.into(),
+            depth_camera_intrinsics,
             world_depth_from_data_depth,
             point_radius_from_world_depth,
+            point_radius_in_world,
             max_depth_in_world: world_depth_from_data_depth * max_data_value,
             depth_dimensions: dimensions,
             depth_data: data,
@@ -1,5 +1,7 @@
 use eframe::epaint::text::TextWrapping;
-use re_data_store::{query_latest_single, EditableAutoValue, EntityPath, EntityPropertyMap};
+use re_data_store::{
+    query_latest_single, DepthUnit, EditableAutoValue, EntityPath, EntityPropertyMap,
+};
 use re_format::format_f32;
 
 use egui::{NumExt, WidgetText};
@@ -219,14 +221,18 @@ impl ViewSpatialState {
         }
 
         if tensor.meaning == TensorDataMeaning::Depth {
-            if properties.depth_from_world_scale.is_auto() {
-                let auto = tensor.meter.unwrap_or_else(|| {
-                    if tensor.dtype().is_integer() {
-                        1000.0
-                    } else {
-                        1.0
-                    }
+            if properties.depth_unit.is_auto() {
+                properties.depth_unit = EditableAutoValue::Auto(if tensor.dtype().is_integer() {
+                    DepthUnit::Millimeters
+                } else {
+                    DepthUnit::Meters
                 });
+            }
+
+            if properties.depth_from_world_scale.is_auto() {
+                let auto = tensor
+                    .meter
+                    .unwrap_or_else(|| properties.depth_unit.get().steps_per_meter());
                 properties.depth_from_world_scale = EditableAutoValue::Auto(auto);
             }
 
@@ -789,8 +795,11 @@ pub fn picking(
                     picking_context.pointer_in_space2d.x.round() as _,
                 ]) {
                     let raw_value = raw_value.as_f64();
-                    let depth_in_meters = raw_value / meter as f64;
-                    depth_at_pointer = Some(depth_in_meters as f32);
+                    // Zero is what stereo depth reports for pixels without a match.
+                    if raw_value > 0.0 && raw_value.is_finite() {
+                        let depth_in_meters = raw_value / meter as f64;
+                        depth_at_pointer = Some(depth_in_meters as f32);
+                    }
                 }
             }
 
@@ -834,6 +843,8 @@ pub fn picking(
                                     &tensor_stats,
                                     &image.annotations,
                                     image.meter,
+                                    image.depth_unit,
+                                    image.color_mapper,
                                     &debug_name,
                                     [coords[0] as _, coords[1] as _],
                                 );
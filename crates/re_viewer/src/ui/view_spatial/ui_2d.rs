@@ -1,7 +1,8 @@
 use eframe::emath::RectTransform;
 use egui::{pos2, vec2, Align2, Color32, NumExt as _, Pos2, Rect, ScrollArea, Shape, Vec2};
 use macaw::IsoTransform;
-use re_data_store::{EntityPath, EntityPropertyMap};
+use re_data_store::{query_latest_single, EntityPath, EntityPropertyMap};
+use re_log_types::component_types::Tensor;
 use re_renderer::view_builder::{TargetConfiguration, ViewBuilder};
 
 use super::{
@@ -299,9 +300,19 @@ fn view_2d_scrollable(
     let points_from_pixels = 1.0 / painter.ctx().pixels_per_point();
     let space_from_pixel = space_from_points * points_from_pixels;
 
-    state
-        .state_2d
-        .update(&response, space_from_ui, scene_rect_accum, available_size);
+    // While the crop region is drawn, dragging draws it instead of panning.
+    let crop_image_size = if ctx.depthai_state.drawing_crop {
+        color_image_size(ctx, space)
+    } else {
+        None
+    };
+    if let Some(image_size) = crop_image_size {
+        crop_drag(ctx, &response, space_from_ui, image_size);
+    } else {
+        state
+            .state_2d
+            .update(&response, space_from_ui, scene_rect_accum, available_size);
+    }
 
     let eye = Eye {
         world_from_view: IsoTransform::IDENTITY,
@@ -394,9 +405,91 @@ fn view_2d_scrollable(
     // Add egui driven labels on top of re_renderer content.
     painter.extend(label_shapes);
 
+    if let Some(image_size) = crop_image_size {
+        painter.extend(crop_overlay(ctx, parent_ui, &ui_from_space, image_size));
+    }
+
     response
 }
 
+/// Size of the color camera image if it's shown in `space`, which the crop region is drawn on.
+fn color_image_size(ctx: &ViewerContext<'_>, space: &EntityPath) -> Option<Vec2> {
+    let entity_path = ctx.depthai_state.color_image_entity_path(space)?;
+    let tensor =
+        query_latest_single::<Tensor>(&ctx.log_db.entity_db, &entity_path, &ctx.current_query())?;
+    let [height, width, _] = tensor.image_height_width_channels()?;
+    Some(vec2(width as f32, height as f32))
+}
+
+/// Drag a rectangle on the color image to crop the color camera to it.
+fn crop_drag(
+    ctx: &mut ViewerContext<'_>,
+    response: &egui::Response,
+    space_from_ui: RectTransform,
+    image_size: Vec2,
+) {
+    let state = &mut *ctx.depthai_state;
+    if response.drag_released() {
+        if let Some((start, end)) = state.crop_drag.take() {
+            let region = Rect::from_two_pos(start, end);
+            // Ignore clicks and accidental tiny drags.
+            if region.width() > 0.01 && region.height() > 0.01 {
+                state.crop_color_camera((region.min.x, region.min.y, region.max.x, region.max.y));
+                state.drawing_crop = false;
+            }
+        }
+        return;
+    }
+    let Some(pointer_pos) = response.interact_pointer_pos() else {
+        return;
+    };
+    let normalized = (space_from_ui.transform_pos(pointer_pos).to_vec2() / image_size)
+        .clamp(Vec2::ZERO, Vec2::splat(1.0))
+        .to_pos2();
+    if response.drag_started() {
+        state.crop_drag = Some((normalized, normalized));
+    } else if let Some((_, end)) = &mut state.crop_drag {
+        *end = normalized;
+    }
+}
+
+fn crop_overlay(
+    ctx: &ViewerContext<'_>,
+    ui: &egui::Ui,
+    ui_from_space: &RectTransform,
+    image_size: Vec2,
+) -> Vec<Shape> {
+    let image_rect = ui_from_space.transform_rect(Rect::from_min_size(Pos2::ZERO, image_size));
+    let color = ui.visuals().selection.stroke.color;
+    let mut shapes = vec![Shape::rect_stroke(
+        image_rect,
+        0.0,
+        egui::Stroke::new(1.0, color.linear_multiply(0.5)),
+    )];
+    if let Some((start, end)) = ctx.depthai_state.crop_drag {
+        let to_ui = |normalized: Pos2| {
+            ui_from_space.transform_pos((normalized.to_vec2() * image_size).to_pos2())
+        };
+        shapes.push(Shape::rect_stroke(
+            Rect::from_two_pos(to_ui(start), to_ui(end)),
+            0.0,
+            egui::Stroke::new(2.0, color),
+        ));
+    } else {
+        shapes.push(ui.fonts(|fonts| {
+            Shape::text(
+                fonts,
+                image_rect.left_top() + vec2(8.0, 8.0),
+                Align2::LEFT_TOP,
+                "Drag to select the region to stream",
+                egui::TextStyle::Body.resolve(ui.style()),
+                color,
+            )
+        }));
+    }
+    shapes
+}
+
 fn setup_target_config(
     painter: &egui::Painter,
     space_from_ui: RectTransform,
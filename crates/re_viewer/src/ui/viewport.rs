@@ -124,6 +124,10 @@ impl Viewport {
                         .data_blueprint
                         .entity_paths()
                 });
+                ctx.depthai_state
+                    .sync_entity_visibility(self.space_views.values_mut());
+                ctx.depthai_state
+                    .sync_color_mappers(self.space_views.values_mut());
                 if !self.visible.is_empty() {
                     ctx.depthai_state.set_subscriptions_from_space_views(
                         self.space_views
@@ -712,13 +716,31 @@ impl<'a, 'b> egui_dock::TabViewer for TabViewer<'a, 'b> {
     }
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        let depthai_state = &self.ctx.depthai_state;
+        // Tell the views of different devices apart, when they show more than one.
+        let label_device = depthai_state
+            .devices_in_space_views(self.space_views.values())
+            .len()
+            > 1;
         let space_view = self
             .space_views
             .get_mut(tab)
             .expect("Should have been populated beforehand");
 
-        let mut text =
-            egui::WidgetText::RichText(egui::RichText::new(space_view.display_name.clone()));
+        let mut display_name = depthai_state
+            .space_view_display_name(space_view.data_blueprint.entity_paths())
+            .unwrap_or(&space_view.display_name)
+            .to_owned();
+        if let Some(device) = space_view
+            .data_blueprint
+            .entity_paths()
+            .iter()
+            .filter(|_| label_device)
+            .find_map(|entity_path| depthai_state.device_for_entity(entity_path))
+        {
+            display_name = format!("{display_name} ({device})");
+        }
+        let mut text = egui::WidgetText::RichText(egui::RichText::new(display_name));
 
         if self.ctx.selection().contains(&Item::SpaceView(*tab)) {
             // Show that it is selected: